@@ -0,0 +1,364 @@
+//! Derive macros of the `overlay` crate, re-exported there with the `derive` feature.
+//!
+//! Parses the item by hand instead of through `syn` to keep the build free of dependencies, so
+//! only plain structs and fieldless enums without generics are supported.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// Implements `overlay::OverlayMenu` for a struct with named fields, building a menu with one
+/// item per field, bound to it: `bool` becomes a `Toggle`, integers and floats a `Number` and any
+/// other type implementing `overlay::Options` a `Select`.
+///
+/// Fields take `#[overlay(...)]` attributes:
+/// - `label = "Text"`: label of the item, defaults to the field name in sentence case.
+/// - `range(min, max)` and `step = n`: bounds and increment of a `Number`, defaulting to the
+///   bounds of the type and `1`.
+/// - `menu = "Name"`: puts the item into a submenu of that name, created where the first of its
+///   items is.
+/// - `skip`: no item for this field.
+#[proc_macro_derive(OverlayMenu, attributes(overlay))]
+pub fn derive_overlay_menu(input: TokenStream) -> TokenStream {
+    match Item::parse(input).and_then(|item| item.menu()) {
+        Ok(code) => code.parse().unwrap(),
+        Err(e) => error(&e),
+    }
+}
+
+/// Implements `overlay::Options` for a fieldless enum, offering its variants in order. Variants
+/// take `#[overlay(label = "Text")]` to change the shown name.
+#[proc_macro_derive(Options, attributes(overlay))]
+pub fn derive_options(input: TokenStream) -> TokenStream {
+    match Item::parse(input).and_then(|item| item.options()) {
+        Ok(code) => code.parse().unwrap(),
+        Err(e) => error(&e),
+    }
+}
+
+fn error(message: &str) -> TokenStream {
+    format!("compile_error!({:?});", message).parse().unwrap()
+}
+
+/// Settings from `#[overlay(...)]` attributes.
+#[derive(Default)]
+struct Attributes {
+    label: Option<String>,
+    range: Option<(String, String)>,
+    step: Option<String>,
+    menu: Option<String>,
+    skip: bool,
+}
+
+impl Attributes {
+    /// Collects the `#[overlay(...)]` attributes at the front of `tokens`, ignoring others.
+    fn take(tokens: &mut &[TokenTree]) -> Result<Self, String> {
+        let mut attributes = Self::default();
+
+        while let [TokenTree::Punct(p), TokenTree::Group(g), rest @ ..] = tokens {
+            if p.as_char() != '#' || g.delimiter() != Delimiter::Bracket {
+                break;
+            }
+            *tokens = rest;
+
+            let inner: Vec<TokenTree> = g.stream().into_iter().collect();
+            let [TokenTree::Ident(name), TokenTree::Group(args)] = inner.as_slice() else {
+                continue;
+            };
+            if name.to_string() != "overlay" {
+                continue;
+            }
+
+            let args: Vec<TokenTree> = args.stream().into_iter().collect();
+            for arg in split(&args, ',') {
+                attributes.set(arg)?;
+            }
+        }
+
+        Ok(attributes)
+    }
+
+    fn set(&mut self, arg: &[TokenTree]) -> Result<(), String> {
+        match arg {
+            [TokenTree::Ident(key)] if key.to_string() == "skip" => self.skip = true,
+            [TokenTree::Ident(key), TokenTree::Group(args)] if key.to_string() == "range" => {
+                let args: Vec<TokenTree> = args.stream().into_iter().collect();
+                let [min, max] = split(&args, ',')[..] else {
+                    return Err(String::from("expected `range(min, max)`"));
+                };
+                self.range = Some((text(min), text(max)));
+            }
+            [TokenTree::Ident(key), TokenTree::Punct(eq), value @ ..] if eq.as_char() == '=' => {
+                match key.to_string().as_str() {
+                    "label" => self.label = Some(string(value)?),
+                    "menu" => self.menu = Some(string(value)?),
+                    "step" => self.step = Some(text(value)),
+                    key => return Err(format!("unknown overlay attribute `{}`", key)),
+                }
+            }
+            [] => {}
+            _ => return Err(format!("unknown overlay attribute `{}`", text(arg))),
+        }
+        Ok(())
+    }
+}
+
+enum Body {
+    /// Name, type and attributes of each field.
+    Struct(Vec<(String, String, Attributes)>),
+    /// Name and attributes of each variant.
+    Enum(Vec<(String, Attributes)>),
+}
+
+struct Item {
+    name: String,
+    body: Body,
+}
+
+impl Item {
+    fn parse(input: TokenStream) -> Result<Self, String> {
+        let tokens: Vec<TokenTree> = input.into_iter().collect();
+        let mut tokens = tokens.as_slice();
+        Attributes::take(&mut tokens)?;
+        skip_visibility(&mut tokens);
+
+        let [TokenTree::Ident(kind), TokenTree::Ident(name), rest @ ..] = tokens else {
+            return Err(String::from("expected a struct or an enum"));
+        };
+        let [TokenTree::Group(body)] = rest else {
+            return Err(String::from("generics and tuple structs are not supported"));
+        };
+        if body.delimiter() != Delimiter::Brace {
+            return Err(String::from("expected named fields or variants"));
+        }
+
+        let body: Vec<TokenTree> = body.stream().into_iter().collect();
+        let body = match kind.to_string().as_str() {
+            "struct" => Body::Struct(
+                split(&body, ',')
+                    .into_iter()
+                    .filter(|field| !field.is_empty())
+                    .map(field)
+                    .collect::<Result<_, _>>()?,
+            ),
+            "enum" => Body::Enum(
+                split(&body, ',')
+                    .into_iter()
+                    .filter(|variant| !variant.is_empty())
+                    .map(variant)
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => return Err(String::from("expected a struct or an enum")),
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            body,
+        })
+    }
+
+    fn menu(&self) -> Result<String, String> {
+        let Body::Struct(fields) = &self.body else {
+            return Err(String::from("OverlayMenu can only be derived for structs"));
+        };
+
+        // Items in order, each either at the top level or in the submenu of the given index.
+        let mut items = String::new();
+        let mut menus: Vec<(String, String)> = vec![];
+
+        for (field, ty, attributes) in fields.iter().filter(|(_, _, a)| !a.skip) {
+            let label = attributes.label.clone().unwrap_or_else(|| sentence(field));
+            let item = item(field, ty, &label, attributes);
+
+            match &attributes.menu {
+                Some(menu) => match menus.iter_mut().find(|(name, _)| name == menu) {
+                    Some((_, items)) => items.push_str(&item),
+                    None => {
+                        items.push_str(&format!("__menu_{},", menus.len()));
+                        menus.push((menu.clone(), item));
+                    }
+                },
+                None => items.push_str(&item),
+            }
+        }
+
+        let submenus: String = menus
+            .iter()
+            .enumerate()
+            .map(|(i, (name, items))| {
+                format!(
+                    "let __menu_{} = ::overlay::Menu::new({:?}, ::overlay::__vec![{}]);",
+                    i, name, items
+                )
+            })
+            .collect();
+
+        Ok(format!(
+            "impl ::overlay::OverlayMenu for {name} {{
+                fn menu(
+                    name: &str,
+                    settings: ::overlay::__Rc<::core::cell::RefCell<Self>>,
+                ) -> ::overlay::Node {{
+                    {submenus}
+                    ::overlay::Menu::new(name, ::overlay::__vec![{items}])
+                }}
+            }}",
+            name = self.name,
+        ))
+    }
+
+    fn options(&self) -> Result<String, String> {
+        let Body::Enum(variants) = &self.body else {
+            return Err(String::from("Options can only be derived for enums"));
+        };
+
+        let variants: String = variants
+            .iter()
+            .map(|(variant, attributes)| {
+                let label = attributes.label.clone().unwrap_or_else(|| variant.clone());
+                format!(
+                    "(::overlay::__String::from({:?}), {}::{}),",
+                    label, self.name, variant
+                )
+            })
+            .collect();
+
+        Ok(format!(
+            "impl ::overlay::Options for {name} {{
+                fn variants() -> ::overlay::__Vec<(::overlay::__String, Self)> {{
+                    ::overlay::__vec![{variants}]
+                }}
+            }}",
+            name = self.name,
+        ))
+    }
+}
+
+/// Expression building the item bound to `field`, followed by a comma.
+fn item(field: &str, ty: &str, label: &str, attributes: &Attributes) -> String {
+    const NUMBERS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize", "f32", "f64",
+    ];
+
+    if ty == "bool" {
+        return format!(
+            "::overlay::Toggle::new({label:?}, settings.borrow().{field}, {{
+                let settings = settings.clone();
+                move |value| settings.borrow_mut().{field} = value
+            }}),"
+        );
+    }
+
+    if NUMBERS.contains(&ty) {
+        let (min, max) = attributes
+            .range
+            .clone()
+            .unwrap_or_else(|| (format!("{ty}::MIN"), format!("{ty}::MAX")));
+        let step = attributes.step.clone().unwrap_or_else(|| String::from("1"));
+        let [min, max, step] = [min, max, step].map(|bound| format!("({bound}) as {ty}"));
+
+        return format!(
+            "::overlay::Number::<{ty}>::new({label:?}, settings.borrow().{field}, {step}, {min}, {max}, {{
+                let settings = settings.clone();
+                move |value| settings.borrow_mut().{field} = *value
+            }}),"
+        );
+    }
+
+    format!(
+        "::overlay::Select::<{ty}>::from_options({label:?}, &settings.borrow().{field}.clone(), {{
+            let settings = settings.clone();
+            move |value| settings.borrow_mut().{field} = value.clone()
+        }}).node(),"
+    )
+}
+
+/// Name, type and attributes of a named field.
+fn field(tokens: &[TokenTree]) -> Result<(String, String, Attributes), String> {
+    let mut tokens = tokens;
+    let attributes = Attributes::take(&mut tokens)?;
+    skip_visibility(&mut tokens);
+
+    let [TokenTree::Ident(name), TokenTree::Punct(colon), ty @ ..] = tokens else {
+        return Err(String::from("expected named fields"));
+    };
+    if colon.as_char() != ':' {
+        return Err(String::from("expected named fields"));
+    }
+
+    Ok((name.to_string(), text(ty), attributes))
+}
+
+/// Name and attributes of a fieldless variant.
+fn variant(tokens: &[TokenTree]) -> Result<(String, Attributes), String> {
+    let mut tokens = tokens;
+    let attributes = Attributes::take(&mut tokens)?;
+
+    match tokens {
+        [TokenTree::Ident(name)] => Ok((name.to_string(), attributes)),
+        [TokenTree::Ident(name), TokenTree::Punct(eq), ..] if eq.as_char() == '=' => {
+            Ok((name.to_string(), attributes))
+        }
+        _ => Err(String::from("Options variants must not have fields")),
+    }
+}
+
+fn skip_visibility(tokens: &mut &[TokenTree]) {
+    if let [TokenTree::Ident(vis), rest @ ..] = tokens
+        && vis.to_string() == "pub"
+    {
+        *tokens = rest;
+        if let [TokenTree::Group(g), rest @ ..] = tokens
+            && g.delimiter() == Delimiter::Parenthesis
+        {
+            *tokens = rest;
+        }
+    }
+}
+
+/// Splits at `separator`s outside of `<...>`.
+fn split(tokens: &[TokenTree], separator: char) -> Vec<&[TokenTree]> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if let TokenTree::Punct(p) = token {
+            match p.as_char() {
+                '<' => depth += 1,
+                '>' if depth > 0 => depth -= 1,
+                c if c == separator && depth == 0 => {
+                    parts.push(&tokens[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+    }
+    parts.push(&tokens[start..]);
+    parts
+}
+
+fn text(tokens: &[TokenTree]) -> String {
+    tokens.iter().cloned().collect::<TokenStream>().to_string()
+}
+
+/// The contents of a string literal.
+fn string(tokens: &[TokenTree]) -> Result<String, String> {
+    let [TokenTree::Literal(literal)] = tokens else {
+        return Err(String::from("expected a string"));
+    };
+    let literal = literal.to_string();
+    literal
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .map(|s| s.replace("\\\"", "\"").replace("\\\\", "\\"))
+        .ok_or_else(|| String::from("expected a string"))
+}
+
+/// `field_name` as `Field name`.
+fn sentence(field: &str) -> String {
+    let mut out = field.replace('_', " ");
+    if let Some(first) = out.get_mut(..1) {
+        first.make_ascii_uppercase();
+    }
+    out
+}
@@ -0,0 +1,53 @@
+/* C interface of the overlay crate, built with the `ffi` feature. See src/ffi.rs.
+ * Link the library built with `cargo rustc --release --features ffi --crate-type staticlib`. */
+
+#ifndef OVERLAY_H
+#define OVERLAY_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct OverlayMenu OverlayMenu;
+typedef struct Overlay Overlay;
+
+/* Gamepad state of one frame; buttons as VPAD_BUTTON_* bits. */
+typedef struct OverlayState {
+    uint32_t hold;
+    uint32_t trigger;
+    uint32_t release;
+    float stick_x;
+    float stick_y;
+} OverlayState;
+
+OverlayMenu *overlay_menu_create(const char *name);
+void overlay_menu_destroy(OverlayMenu *menu);
+/* Takes ownership of submenu, unless it is menu itself. */
+void overlay_menu_add_submenu(OverlayMenu *menu, OverlayMenu *submenu);
+/* Items given a NULL callback are not added. */
+void overlay_menu_add_toggle(OverlayMenu *menu, const char *text, bool value,
+                             void (*f)(bool value, void *user), void *user);
+void overlay_menu_add_number(OverlayMenu *menu, const char *text, int32_t value, int32_t inc,
+                             int32_t min, int32_t max, void (*f)(int32_t value, void *user),
+                             void *user);
+void overlay_menu_add_button(OverlayMenu *menu, const char *text, void (*f)(void *user),
+                             void *user);
+
+/* Take ownership of root. overlay_create requires the `notifications` feature; overlay_create_raw
+   returns NULL if sink is NULL. */
+Overlay *overlay_create(OverlayMenu *root);
+Overlay *overlay_create_raw(OverlayMenu *root, void (*sink)(const char *line, void *user),
+                            void *user);
+void overlay_run(Overlay *overlay, const OverlayState *state, uint32_t combo);
+bool overlay_captures_input(const Overlay *overlay);
+uint32_t overlay_consumed_buttons(const Overlay *overlay);
+void overlay_destroy(Overlay *overlay);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,114 @@
+//! Queue of what the user does in the overlay, for hosts reacting in one place instead of in the
+//! callback of every item.
+//!
+//! Each [Subscriber] gets its own copy of every event emitted after it subscribed. Items are
+//! named by their [crate::MenuItem::id], or their label if they have none. Like the rest of the
+//! overlay, events must only be used from one thread.
+
+use crate::{Node, Value, global::Global, name};
+use wut::{alloc::collections::VecDeque, prelude::*};
+
+/// Events a [Subscriber] keeps before dropping the oldest.
+const CAPACITY: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// The value of an item changed through input.
+    ValueChanged {
+        id: String,
+        value: Value,
+    },
+    /// A was pressed on a plain action, e.g. a [crate::Button].
+    Activated {
+        id: String,
+    },
+    /// An item with entries, e.g. a [crate::Menu], was pushed onto the navigation stack.
+    MenuEntered {
+        id: String,
+    },
+    OverlayOpened,
+    OverlayClosed,
+}
+
+/// Queues of the live subscribers by their id.
+struct Subscribers {
+    next: u64,
+    queues: Vec<(u64, VecDeque<Event>)>,
+}
+
+impl Subscribers {
+    fn queue(&mut self, id: u64) -> Option<&mut VecDeque<Event>> {
+        self.queues
+            .iter_mut()
+            .find(|(queue, _)| *queue == id)
+            .map(|(_, queue)| queue)
+    }
+}
+
+static SUBSCRIBERS: Global<Subscribers> = Global::new(Subscribers {
+    next: 0,
+    queues: Vec::new(),
+});
+
+/// Receives the events emitted while it is alive.
+pub struct Subscriber {
+    id: u64,
+}
+
+impl Subscriber {
+    /// Oldest event not taken yet.
+    pub fn poll(&self) -> Option<Event> {
+        SUBSCRIBERS.with(|subscribers| subscribers.queue(self.id)?.pop_front())
+    }
+
+    /// Takes all pending events, oldest first.
+    pub fn drain(&self) -> Vec<Event> {
+        SUBSCRIBERS.with(|subscribers| {
+            subscribers
+                .queue(self.id)
+                .map(|queue| queue.drain(..).collect())
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        SUBSCRIBERS.with(|subscribers| subscribers.queues.retain(|(id, _)| *id != self.id));
+    }
+}
+
+pub fn subscribe() -> Subscriber {
+    SUBSCRIBERS.with(|subscribers| {
+        let id = subscribers.next;
+        subscribers.next += 1;
+        subscribers
+            .queues
+            .push((id, VecDeque::with_capacity(CAPACITY)));
+        Subscriber { id }
+    })
+}
+
+/// Passes `event` to every live subscriber.
+pub(crate) fn emit(event: Event) {
+    SUBSCRIBERS.with(|subscribers| {
+        for (_, queue) in &mut subscribers.queues {
+            if queue.len() == CAPACITY {
+                queue.pop_front();
+            }
+            queue.push_back(event.clone());
+        }
+    });
+}
+
+/// Whether anyone would receive an event, to skip building it otherwise.
+pub(crate) fn subscribed() -> bool {
+    SUBSCRIBERS.with(|subscribers| !subscribers.queues.is_empty())
+}
+
+/// Name of `node` in events.
+pub(crate) fn id(node: &Node) -> String {
+    node.try_borrow()
+        .map(|item| name(&**item))
+        .unwrap_or_default()
+}
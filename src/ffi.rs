@@ -0,0 +1,296 @@
+//! C interface for building menus and running the overlay from C/C++ plugins.
+//!
+//! Menus are assembled through [OverlayMenu] handles and handed to [overlay_create], which takes
+//! ownership of them. Callbacks receive the new value and the `user` pointer given with them.
+//! The declarations are in `include/overlay.h`.
+//!
+//! C plugins link the static library built with
+//! `cargo rustc --release --features ffi --crate-type staticlib`.
+//!
+//! Strings are NUL terminated UTF-8; invalid ones are treated as empty. Items given a null
+//! callback are not added.
+
+use crate::{Button, Menu, Node, Number, Overlay, RawOverlay, Toggle};
+use core::ffi::{CStr, c_char, c_void};
+use wut::{
+    alloc::boxed::Box,
+    flagset::FlagSet,
+    gamepad::{Joystick, State},
+    prelude::*,
+};
+
+/// Menu under construction.
+pub struct OverlayMenu {
+    name: String,
+    items: Vec<Node>,
+}
+
+impl OverlayMenu {
+    fn build(self) -> Node {
+        Menu::new(&self.name, self.items)
+    }
+}
+
+/// Gamepad state of one frame; buttons as `VPAD_BUTTON_*` bits.
+#[repr(C)]
+pub struct OverlayState {
+    pub hold: u32,
+    pub trigger: u32,
+    pub release: u32,
+    pub stick_x: f32,
+    pub stick_y: f32,
+}
+
+impl From<&OverlayState> for State {
+    fn from(state: &OverlayState) -> Self {
+        State {
+            hold: FlagSet::new_truncated(state.hold),
+            trigger: FlagSet::new_truncated(state.trigger),
+            release: FlagSet::new_truncated(state.release),
+            left_stick: Some(Joystick {
+                x: state.stick_x,
+                y: state.stick_y,
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+/// # Safety
+///
+/// `ptr` is null or a NUL terminated string.
+unsafe fn text(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    String::from(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap_or(""))
+}
+
+/// Pointers are only passed back to the callbacks they were given with.
+struct User(*mut c_void);
+
+impl User {
+    fn get(&self) -> *mut c_void {
+        self.0
+    }
+}
+
+/// Creates an empty menu.
+///
+/// # Safety
+///
+/// `name` is null or a NUL terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_menu_create(name: *const c_char) -> *mut OverlayMenu {
+    Box::into_raw(Box::new(OverlayMenu {
+        name: unsafe { text(name) },
+        items: vec![],
+    }))
+}
+
+/// Frees a menu that was not added to another one or to an overlay.
+///
+/// # Safety
+///
+/// `menu` comes from [overlay_menu_create] and is not used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_menu_destroy(menu: *mut OverlayMenu) {
+    if !menu.is_null() {
+        drop(unsafe { Box::from_raw(menu) });
+    }
+}
+
+/// Adds `submenu` to the end of `menu`, taking ownership of it. A menu is not added to itself.
+///
+/// # Safety
+///
+/// Both come from [overlay_menu_create]; `submenu` is not used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_menu_add_submenu(
+    menu: *mut OverlayMenu,
+    submenu: *mut OverlayMenu,
+) {
+    if submenu.is_null() || submenu == menu {
+        return;
+    }
+    let Some(menu) = (unsafe { menu.as_mut() }) else {
+        return;
+    };
+    let submenu = unsafe { Box::from_raw(submenu) };
+    menu.items.push(submenu.build());
+}
+
+/// Adds a [Toggle] calling `f` with the new state.
+///
+/// # Safety
+///
+/// `menu` comes from [overlay_menu_create], `text` is null or a NUL terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_menu_add_toggle(
+    menu: *mut OverlayMenu,
+    text: *const c_char,
+    value: bool,
+    f: Option<extern "C" fn(bool, *mut c_void)>,
+    user: *mut c_void,
+) {
+    let (Some(menu), Some(f)) = (unsafe { menu.as_mut() }, f) else {
+        return;
+    };
+    let user = User(user);
+    menu.items.push(Toggle::new(
+        &unsafe { self::text(text) },
+        value,
+        move |value| f(value, user.get()),
+    ));
+}
+
+/// Adds a [Number] calling `f` with the applied value.
+///
+/// # Safety
+///
+/// `menu` comes from [overlay_menu_create], `text` is null or a NUL terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_menu_add_number(
+    menu: *mut OverlayMenu,
+    text: *const c_char,
+    value: i32,
+    inc: i32,
+    min: i32,
+    max: i32,
+    f: Option<extern "C" fn(i32, *mut c_void)>,
+    user: *mut c_void,
+) {
+    let (Some(menu), Some(f)) = (unsafe { menu.as_mut() }, f) else {
+        return;
+    };
+    let user = User(user);
+    menu.items.push(Number::new(
+        &unsafe { self::text(text) },
+        value,
+        inc,
+        min,
+        max,
+        move |value| f(*value, user.get()),
+    ));
+}
+
+/// Adds a [Button] calling `f` when pressed.
+///
+/// # Safety
+///
+/// `menu` comes from [overlay_menu_create], `text` is null or a NUL terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_menu_add_button(
+    menu: *mut OverlayMenu,
+    text: *const c_char,
+    f: Option<extern "C" fn(*mut c_void)>,
+    user: *mut c_void,
+) {
+    let (Some(menu), Some(f)) = (unsafe { menu.as_mut() }, f) else {
+        return;
+    };
+    let user = User(user);
+    menu.items
+        .push(Button::new(&unsafe { self::text(text) }, move || {
+            f(user.get())
+        }));
+}
+
+/// Creates an overlay shown as a notification with `root` as its menu, taking ownership of it.
+///
+/// # Safety
+///
+/// `root` comes from [overlay_menu_create] and is not used afterwards.
+#[cfg(feature = "notifications")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_create(root: *mut OverlayMenu) -> *mut Overlay {
+    if root.is_null() {
+        return core::ptr::null_mut();
+    }
+    let root = unsafe { Box::from_raw(root) };
+    Box::into_raw(Box::new(Overlay::new(root.build())))
+}
+
+/// Creates an overlay passing its line to `sink` instead of showing a notification, see
+/// [RawOverlay]. Takes ownership of `root`, which is freed if `sink` is null.
+///
+/// # Safety
+///
+/// `root` comes from [overlay_menu_create] and is not used afterwards. The line passed to `sink`
+/// is only valid during the call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_create_raw(
+    root: *mut OverlayMenu,
+    sink: Option<extern "C" fn(*const c_char, *mut c_void)>,
+    user: *mut c_void,
+) -> *mut Overlay {
+    if root.is_null() {
+        return core::ptr::null_mut();
+    }
+    let root = unsafe { Box::from_raw(root) };
+    let Some(sink) = sink else {
+        return core::ptr::null_mut();
+    };
+    let user = User(user);
+    let raw = RawOverlay::new(root.build(), move |line| {
+        let mut line = line.as_bytes().to_vec();
+        line.retain(|&b| b != 0);
+        line.push(0);
+        sink(line.as_ptr() as *const c_char, user.get());
+    });
+
+    Box::into_raw(Box::new(raw.into_inner()))
+}
+
+/// Runs one frame, see [Overlay::run]. `combo` is a `VPAD_BUTTON_*` mask.
+///
+/// # Safety
+///
+/// `overlay` comes from [overlay_create] or [overlay_create_raw], `state` points to a valid
+/// [OverlayState].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_run(
+    overlay: *mut Overlay,
+    state: *const OverlayState,
+    combo: u32,
+) {
+    let (Some(overlay), Some(state)) = (unsafe { overlay.as_mut() }, unsafe { state.as_ref() })
+    else {
+        return;
+    };
+    overlay.run(State::from(state), FlagSet::new_truncated(combo));
+}
+
+/// Whether the overlay uses the input, see [Overlay::captures_input].
+///
+/// # Safety
+///
+/// `overlay` comes from [overlay_create] or [overlay_create_raw].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_captures_input(overlay: *const Overlay) -> bool {
+    unsafe { overlay.as_ref() }.is_some_and(Overlay::captures_input)
+}
+
+/// `VPAD_BUTTON_*` mask of the buttons the overlay used, see [Overlay::consumed].
+///
+/// # Safety
+///
+/// `overlay` comes from [overlay_create] or [overlay_create_raw].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_consumed_buttons(overlay: *const Overlay) -> u32 {
+    unsafe { overlay.as_ref() }.map_or(0, |overlay| overlay.consumed().bits())
+}
+
+/// Hides and frees the overlay with its menus.
+///
+/// # Safety
+///
+/// `overlay` comes from [overlay_create] or [overlay_create_raw] and is not used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn overlay_destroy(overlay: *mut Overlay) {
+    if overlay.is_null() {
+        return;
+    }
+    let mut overlay = unsafe { Box::from_raw(overlay) };
+    overlay.hide();
+}
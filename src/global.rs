@@ -0,0 +1,58 @@
+//! State shared crate-wide instead of per [crate::Overlay], e.g. the faulted items of the
+//! [crate::watchdog].
+
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Value only reached through [Global::with], which lends it to one caller at a time.
+pub(crate) struct Global<T> {
+    busy: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: `with` hands out at most one reference to the value at a time, also across threads,
+// and the value may be reached from any of them as it is `Send`. References from the value do not
+// escape the closure.
+unsafe impl<T: Send> Sync for Global<T> {}
+
+impl<T> Global<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        Self {
+            busy: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Runs `f` on the value.
+    ///
+    /// # Panics
+    ///
+    /// If the value is already lent out, i.e. when called from within `f` or while another
+    /// thread is in `with`.
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        self.try_with(f)
+            .expect("overlay state accessed while in use")
+    }
+
+    /// Like [Global::with], returning `None` instead of panicking, e.g. from a panic hook.
+    pub(crate) fn try_with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        struct Release<'a>(&'a AtomicBool);
+
+        impl Drop for Release<'_> {
+            fn drop(&mut self) {
+                self.0.store(false, Ordering::Release);
+            }
+        }
+
+        if self.busy.swap(true, Ordering::Acquire) {
+            return None;
+        }
+        let _release = Release(&self.busy);
+
+        // SAFETY: `busy` was clear, so no other reference to the value exists until `_release`
+        // is dropped.
+        Some(f(unsafe { &mut *self.value.get() }))
+    }
+}
@@ -0,0 +1,99 @@
+//! Pool of shared label strings.
+//!
+//! Generated menus repeat the same labels many times ("Enabled", "Speed", ...), so the built-in
+//! widgets keep their text as an [Arc<str>] from this pool instead of allocating it once per
+//! item. Strings no widget holds anymore are dropped from the pool once it has doubled in size
+//! since it was last swept.
+
+use crate::global::Global;
+use wut::alloc::{collections::BTreeSet, sync::Arc};
+
+/// Strings in the pool below which it is not swept.
+const SWEEP_MIN: usize = 64;
+
+struct Pool {
+    strings: BTreeSet<Arc<str>>,
+    /// Size of the pool after the last sweep.
+    swept: usize,
+    #[cfg(feature = "instrumentation")]
+    stats: Stats,
+}
+
+static POOL: Global<Pool> = Global::new(Pool {
+    strings: BTreeSet::new(),
+    swept: 0,
+    #[cfg(feature = "instrumentation")]
+    stats: Stats {
+        strings: 0,
+        bytes: 0,
+        hits: 0,
+        saved: 0,
+        dropped: 0,
+    },
+});
+
+/// Usage of the pool since startup.
+#[cfg(feature = "instrumentation")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Distinct strings in the pool.
+    pub strings: usize,
+    /// Bytes of the distinct strings.
+    pub bytes: usize,
+    /// Lookups that found the string already in the pool.
+    pub hits: usize,
+    /// Bytes not allocated thanks to those hits.
+    pub saved: usize,
+    /// Strings dropped by sweeps as no widget held them anymore.
+    pub dropped: usize,
+}
+
+/// The shared copy of `s`.
+pub fn intern(s: &str) -> Arc<str> {
+    POOL.with(|pool| {
+        if let Some(shared) = pool.strings.get(s) {
+            #[cfg(feature = "instrumentation")]
+            {
+                pool.stats.hits += 1;
+                pool.stats.saved += s.len();
+            }
+            return shared.clone();
+        }
+
+        if pool.strings.len() >= SWEEP_MIN.max(pool.swept * 2) {
+            pool.sweep();
+        }
+
+        let shared: Arc<str> = Arc::from(s);
+        pool.strings.insert(shared.clone());
+        #[cfg(feature = "instrumentation")]
+        {
+            pool.stats.strings += 1;
+            pool.stats.bytes += s.len();
+        }
+        shared
+    })
+}
+
+impl Pool {
+    /// Drops the strings only the pool holds.
+    fn sweep(&mut self) {
+        self.strings.retain(|shared| {
+            let kept = Arc::strong_count(shared) > 1;
+            #[cfg(feature = "instrumentation")]
+            if !kept {
+                self.stats.strings -= 1;
+                self.stats.bytes -= shared.len();
+                self.stats.dropped += 1;
+            }
+            kept
+        });
+        self.swept = self.strings.len();
+    }
+}
+
+/// Current [Stats] of the pool.
+#[cfg(feature = "instrumentation")]
+pub fn stats() -> Stats {
+    POOL.with(|pool| pool.stats)
+}
@@ -1,445 +1,8926 @@
 #![no_std]
 
-use core::{cell::RefCell, fmt::Display};
+#[cfg(feature = "simulator")]
+extern crate std;
+
+pub mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod global;
+pub mod intern;
+#[cfg(feature = "osscreen")]
+pub mod osscreen;
+pub mod persist;
+pub mod practice;
+pub mod render;
+#[cfg(feature = "simulator")]
+pub mod simulator;
+pub mod sound;
+pub mod status;
+pub mod theme;
+pub mod timings;
+pub mod watchdog;
+pub mod wups;
+
+use core::{
+    any::Any,
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Display, Write},
+    marker::PhantomData,
+    sync::atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering},
+};
+#[cfg(feature = "notifications")]
 use notifications;
 use wut::{
-    alloc::{boxed::Box, rc::Rc},
+    alloc::{
+        boxed::Box,
+        collections::VecDeque,
+        rc::{Rc, Weak},
+        sync::Arc,
+    },
     flagset::FlagSet,
-    font::icons,
     gamepad::State,
     prelude::*,
 };
 
 pub type Node = Rc<RefCell<Box<dyn MenuItem>>>;
 
-pub trait MenuItem {
+/// Error slot shared between a `try_*` constructed item and its wrapped callback.
+#[derive(Default, Clone)]
+struct Failure(Rc<Cell<Option<String>>>);
+
+impl Failure {
+    fn record(&self, result: Result<(), String>) {
+        if let Err(e) = result {
+            self.0.set(Some(e));
+        }
+    }
+
+    fn take(&self) -> Option<String> {
+        self.0.take()
+    }
+}
+
+/// [MenuItem::render] for items implementing [MenuItem::render_into].
+fn rendered(item: &dyn MenuItem) -> String {
+    let mut out = String::new();
+    item.render_into(&mut out);
+    out
+}
+
+/// Writes a bar of `segments` segments with the first `filled` filled, e.g. `[===-----]`.
+fn bar(out: &mut dyn Write, filled: u32, segments: u32) {
+    let theme = theme::current();
+    let _ = out.write_str("[");
+    for i in 0..segments {
+        let _ = out.write_str(if i < filled {
+            theme.bar_filled
+        } else {
+            theme.bar_empty
+        });
+    }
+    let _ = out.write_str("]");
+}
+
+/// Access to an item as [Any], so a [TypedNode] can check the type of the widget it holds.
+/// Implemented for every `'static` type.
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+pub trait MenuItem: AsAny {
     fn render(&self) -> String;
 
+    /// Writes the rendering to `out`, letting composite items render their children without an
+    /// intermediate [String] each. Defaults to [MenuItem::render].
+    fn render_into(&self, out: &mut dyn Write) {
+        let _ = out.write_str(&self.render());
+    }
+
+    /// Renders into `buf` and returns the number of bytes written. Built-in widgets with plain
+    /// text write directly without allocating; the default goes through [MenuItem::render].
+    #[cfg(feature = "byte-render")]
+    fn render_bytes(&self, buf: &mut [u8]) -> usize {
+        ByteWriter::new(buf).push_str(&self.render()).len()
+    }
+
     fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool;
 
+    /// Like [MenuItem::control], with the [MenuContext] of the overlay handling the input for
+    /// callbacks to act on it. Items passing input on forward the context; the default drops it.
+    fn control_ctx(&mut self, input: State, stack: &mut Vec<Node>, _ctx: &mut MenuContext) -> bool {
+        self.control(input, stack)
+    }
+
+    /// Called once per [Overlay::run], whether the overlay is open or not. Returns whether the
+    /// rendering changed.
+    fn tick(&mut self) -> bool {
+        false
+    }
+
+    /// Like [MenuItem::tick], with the [MenuContext] of the overlay, see
+    /// [MenuItem::control_ctx].
+    fn tick_ctx(&mut self, _ctx: &mut MenuContext) -> bool {
+        self.tick()
+    }
+
+    /// Called when the item is pushed onto the navigation stack.
     fn focus(&mut self) {}
 
-    fn focusable(&self) -> bool {
+    /// Called when the item is popped off the navigation stack.
+    fn blur(&mut self) {}
+
+    /// Whether `node`, directly above this item on the navigation stack, is still part of it.
+    ///
+    /// Stack entries above an item returning `false` are popped.
+    fn owns(&self, _node: &Node) -> bool {
+        true
+    }
+
+    /// Items shown in place after this one by the parent [Menu], e.g. an expanded [Section].
+    /// Owned, so decorators can hand out those of the item they wrap; empty ones do not allocate.
+    fn inline(&self) -> Vec<Node> {
+        vec![]
+    }
+
+    /// Items nested below this one, for tree traversal.
+    fn children(&self) -> &[Node] {
+        &[]
+    }
+
+    /// Item this one wraps for decoration, e.g. a [Badge]. Lookups by path go through it.
+    fn decorates(&self) -> Option<&Node> {
+        None
+    }
+
+    /// Writes the rendering with `badge` attached, see [Badge]. Defaults to appending it in
+    /// parentheses.
+    fn render_decorated(&self, badge: &str, out: &mut dyn Write) {
+        self.render_into(out);
+        let _ = write!(out, " ({})", badge);
+    }
+
+    /// Rendering of the item as seen from its parent, e.g. in a tree dump.
+    fn summary(&self) -> String {
+        self.render()
+    }
+
+    /// Called when the cursor of the parent moves onto (`true`) or away from (`false`) the item.
+    fn select(&mut self, _selected: bool) {}
+
+    /// Whether the items below this one can currently be reached by the user.
+    fn accessible(&self) -> bool {
+        true
+    }
+
+    /// Whether the item is a plain action that [MenuItem::activate] can trigger.
+    fn actionable(&self) -> bool {
         false
     }
-}
 
-// region: Menu
+    /// Performs the action of the item as if A was pressed on it.
+    fn activate(&mut self) {}
 
-pub struct Menu {
-    name: String,
-    items: Vec<Node>,
-    pos: usize,
-    focused: bool,
-}
+    /// Like [MenuItem::activate], with the [MenuContext] of the overlay, see
+    /// [MenuItem::control_ctx].
+    fn activate_ctx(&mut self, _ctx: &mut MenuContext) {
+        self.activate()
+    }
 
-impl Menu {
-    pub fn new(name: &str, items: Vec<Node>) -> Node {
-        Rc::new(RefCell::new(Box::new(Self {
-            name: String::from(name),
-            items,
-            pos: 0,
-            focused: false,
-        })))
+    /// Name addressing the item in paths like `"Video/Gamma"`, see [find_path].
+    fn label(&self) -> String {
+        String::new()
     }
-}
 
-impl MenuItem for Menu {
-    fn focus(&mut self) {
-        self.focused = true;
+    /// Whether the item currently applies. A parent [Menu] marks disabled items with
+    /// [theme::Theme::disabled], skips them when moving the cursor and passes them no input.
+    fn enabled(&self) -> bool {
+        true
+    }
+
+    /// Enables or disables the item, see [MenuItem::enabled]. Returns whether the item supports
+    /// being disabled.
+    fn set_enabled(&mut self, _enabled: bool) -> bool {
+        false
+    }
+
+    /// Explanation shown below the menu line while the cursor is on the item, see
+    /// [Overlay::set_help_delay]. Given to built-in items with [Help].
+    fn help(&self) -> Option<String> {
+        None
+    }
+
+    /// Tones played for the item instead of those of the theme. Given to built-in items with
+    /// [Cues].
+    fn sounds(&self) -> Option<sound::Sounds> {
+        None
+    }
+
+    /// Stable name for looking the item up with [find], independent of its label. Given to
+    /// built-in items with [Id].
+    fn id(&self) -> Option<&str> {
+        None
+    }
+
+    /// The value the item was created with, if it can be reset to it with
+    /// [MenuItem::set_value].
+    fn default_value(&self) -> Option<Value> {
+        None
+    }
+
+    /// Error returned by a callback of a `try_*` constructed item since the last call. The
+    /// [Overlay] shows it as a toast.
+    fn take_error(&mut self) -> Option<String> {
+        None
+    }
+
+    /// Short `label: value` form shown by a parent [Menu] created with [Menu::previewing].
+    fn preview(&self) -> Option<String> {
+        let label = self.label();
+        match self.value() {
+            Some(value) if !label.is_empty() => Some(format!("{}: {}", label, value)),
+            _ => None,
+        }
+    }
+
+    /// The entry the cursor is on, for items navigating between others.
+    fn current(&self) -> Option<Node> {
+        None
+    }
+
+    /// Moves the cursor onto `entry`, for navigating programmatically. Returns whether `entry` is
+    /// one of the entries of this item.
+    fn select_entry(&mut self, _entry: &Node) -> bool {
+        false
+    }
+
+    /// Current value, for items that have one.
+    fn value(&self) -> Option<Value> {
+        None
+    }
+
+    /// Sets the value and invokes the callback like a change by the user would. Returns whether
+    /// the value was accepted.
+    fn set_value(&mut self, _value: &Value) -> bool {
+        false
+    }
+
+    /// Whether [MenuItem::set_value] would accept `value`, without setting it. Defaults to
+    /// values of the same kind as [MenuItem::value].
+    fn accepts(&self, value: &Value) -> bool {
+        self.value()
+            .is_some_and(|v| core::mem::discriminant(&v) == core::mem::discriminant(value))
     }
 
     fn focusable(&self) -> bool {
+        false
+    }
+
+    /// Whether the cursor of a parent [Menu] stops on the item. Display-only items like a
+    /// [Separator] return `false` and are skipped.
+    fn interactive(&self) -> bool {
         true
     }
 
-    fn render(&self) -> String {
-        if self.focused {
-            format!(
-                "{}\u{3000}{}\u{3000}{}",
-                icons::BTN_LEFT,
-                &self.items[self.pos].borrow().render(),
-                icons::BTN_RIGHT
-            )
-        } else {
-            format!("{} {}", self.name, icons::KBD_RETURN)
+    /// Whether `input` on the selected item makes the parent [Menu] push it onto the navigation
+    /// stack. Defaults to A on focusable items.
+    fn enters(&self, input: &State) -> bool {
+        self.focusable() && input.trigger.contains(wut::gamepad::Button::A)
+    }
+}
+
+/// Name recognizing `item` outside of the tree, e.g. in events or kept faults: its
+/// [MenuItem::id], or its label if it has none.
+pub(crate) fn name(item: &dyn MenuItem) -> String {
+    item.id().map(String::from).unwrap_or_else(|| item.label())
+}
+
+/// Implements the listed [MenuItem] methods of a decorator by forwarding them to the item in its
+/// `inner` field, which `children` and `decorates` hand out. The methods the decorator changes
+/// are written out next to the invocation.
+macro_rules! forward_menu_item {
+    ($($method:ident),* $(,)?) => {
+        $(forward_menu_item!(@ $method);)*
+    };
+    (@ render) => {
+        fn render(&self) -> String {
+            self.inner.borrow().render()
+        }
+    };
+    (@ render_into) => {
+        fn render_into(&self, out: &mut dyn Write) {
+            self.inner.borrow().render_into(out);
+        }
+    };
+    (@ render_bytes) => {
+        #[cfg(feature = "byte-render")]
+        fn render_bytes(&self, buf: &mut [u8]) -> usize {
+            self.inner.borrow().render_bytes(buf)
+        }
+    };
+    (@ render_decorated) => {
+        fn render_decorated(&self, badge: &str, out: &mut dyn Write) {
+            self.inner.borrow().render_decorated(badge, out);
+        }
+    };
+    (@ control) => {
+        fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+            self.inner.borrow_mut().control(input, stack)
+        }
+    };
+    (@ control_ctx) => {
+        fn control_ctx(
+            &mut self,
+            input: State,
+            stack: &mut Vec<Node>,
+            ctx: &mut MenuContext,
+        ) -> bool {
+            self.inner.borrow_mut().control_ctx(input, stack, ctx)
+        }
+    };
+    (@ tick) => {
+        fn tick(&mut self) -> bool {
+            self.inner.borrow_mut().tick()
+        }
+    };
+    (@ tick_ctx) => {
+        fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+            self.inner.borrow_mut().tick_ctx(ctx)
+        }
+    };
+    (@ focus) => {
+        fn focus(&mut self) {
+            self.inner.borrow_mut().focus();
+        }
+    };
+    (@ blur) => {
+        fn blur(&mut self) {
+            self.inner.borrow_mut().blur();
+        }
+    };
+    (@ owns) => {
+        fn owns(&self, node: &Node) -> bool {
+            self.inner.borrow().owns(node)
+        }
+    };
+    (@ inline) => {
+        fn inline(&self) -> Vec<Node> {
+            self.inner.borrow().inline()
+        }
+    };
+    (@ children) => {
+        fn children(&self) -> &[Node] {
+            core::slice::from_ref(&self.inner)
+        }
+    };
+    (@ decorates) => {
+        fn decorates(&self) -> Option<&Node> {
+            Some(&self.inner)
+        }
+    };
+    (@ summary) => {
+        fn summary(&self) -> String {
+            self.inner.borrow().summary()
+        }
+    };
+    (@ select) => {
+        fn select(&mut self, selected: bool) {
+            self.inner.borrow_mut().select(selected);
+        }
+    };
+    (@ accessible) => {
+        fn accessible(&self) -> bool {
+            self.inner.borrow().accessible()
+        }
+    };
+    (@ actionable) => {
+        fn actionable(&self) -> bool {
+            self.inner.borrow().actionable()
+        }
+    };
+    (@ activate) => {
+        fn activate(&mut self) {
+            self.inner.borrow_mut().activate();
+        }
+    };
+    (@ activate_ctx) => {
+        fn activate_ctx(&mut self, ctx: &mut MenuContext) {
+            self.inner.borrow_mut().activate_ctx(ctx);
+        }
+    };
+    (@ label) => {
+        fn label(&self) -> String {
+            self.inner.borrow().label()
+        }
+    };
+    (@ enabled) => {
+        fn enabled(&self) -> bool {
+            self.inner.borrow().enabled()
+        }
+    };
+    (@ set_enabled) => {
+        fn set_enabled(&mut self, enabled: bool) -> bool {
+            self.inner.borrow_mut().set_enabled(enabled)
+        }
+    };
+    (@ help) => {
+        fn help(&self) -> Option<String> {
+            self.inner.borrow().help()
+        }
+    };
+    (@ sounds) => {
+        fn sounds(&self) -> Option<sound::Sounds> {
+            self.inner.borrow().sounds()
+        }
+    };
+    (@ default_value) => {
+        fn default_value(&self) -> Option<Value> {
+            self.inner.borrow().default_value()
+        }
+    };
+    (@ take_error) => {
+        fn take_error(&mut self) -> Option<String> {
+            self.inner.borrow_mut().take_error()
+        }
+    };
+    (@ preview) => {
+        fn preview(&self) -> Option<String> {
+            self.inner.borrow().preview()
+        }
+    };
+    (@ current) => {
+        fn current(&self) -> Option<Node> {
+            self.inner.borrow().current()
+        }
+    };
+    (@ select_entry) => {
+        fn select_entry(&mut self, entry: &Node) -> bool {
+            self.inner.borrow_mut().select_entry(entry)
+        }
+    };
+    (@ value) => {
+        fn value(&self) -> Option<Value> {
+            self.inner.borrow().value()
+        }
+    };
+    (@ set_value) => {
+        fn set_value(&mut self, value: &Value) -> bool {
+            self.inner.borrow_mut().set_value(value)
+        }
+    };
+    (@ accepts) => {
+        fn accepts(&self, value: &Value) -> bool {
+            self.inner.borrow().accepts(value)
+        }
+    };
+    (@ focusable) => {
+        fn focusable(&self) -> bool {
+            self.inner.borrow().focusable()
+        }
+    };
+    (@ interactive) => {
+        fn interactive(&self) -> bool {
+            self.inner.borrow().interactive()
+        }
+    };
+    (@ enters) => {
+        fn enters(&self, input: &State) -> bool {
+            self.inner.borrow().enters(input)
+        }
+    };
+}
+
+// region: Value
+
+/// Dynamically typed item value, see [MenuItem::value].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    /// Position within the options of a [Select].
+    Index(usize),
+    Text(String),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Value::Bool(true) => write!(f, "on"),
+            Value::Bool(false) => write!(f, "off"),
+            Value::Int(v) => write!(f, "{}", v),
+            Value::Float(v) => write!(f, "{:.2}", v),
+            Value::Index(i) => write!(f, "#{}", i),
+            Value::Text(text) => write!(f, "{}", text),
         }
     }
+}
 
-    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
-        use wut::gamepad::Button as B;
-        let mut changed = false;
+/// Conversion of [Number] values from and to [Value].
+pub trait NumericValue: Sized {
+    fn to_value(&self) -> Value;
 
-        let item = self.items[self.pos].clone();
+    fn from_value(value: &Value) -> Option<Self>;
+}
 
-        if item.borrow().focusable() && input.trigger.contains(B::A) {
-            item.borrow_mut().focus();
-            stack.push(item);
-            changed = true;
-        } else if input.trigger.contains(B::B) {
-            if stack.len() > 1 {
-                self.focused = false;
-                stack.pop();
-                changed = true;
+macro_rules! impl_numeric_value {
+    (int: $($t:ty),*) => {
+        $(
+            impl NumericValue for $t {
+                fn to_value(&self) -> Value {
+                    Value::Int(*self as i64)
+                }
+
+                fn from_value(value: &Value) -> Option<Self> {
+                    match value {
+                        Value::Int(v) => Self::try_from(*v).ok(),
+                        Value::Float(v) => Some(*v as $t),
+                        _ => None,
+                    }
+                }
             }
-        } else if input.trigger.contains(B::Left) {
-            self.pos = (self.pos + self.items.len() - 1) % self.items.len();
-            changed = true;
-        } else if input.trigger.contains(B::Right) {
-            self.pos = (self.pos + 1) % self.items.len();
-            changed = true;
-        } else {
-            changed = self.items[self.pos].borrow_mut().control(input, stack);
+        )*
+    };
+    (float: $($t:ty),*) => {
+        $(
+            impl NumericValue for $t {
+                fn to_value(&self) -> Value {
+                    Value::Float(*self as f64)
+                }
+
+                fn from_value(value: &Value) -> Option<Self> {
+                    match value {
+                        Value::Int(v) => Some(*v as $t),
+                        Value::Float(v) => Some(*v as $t),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_value!(int: i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_numeric_value!(float: f32, f64);
+
+/// Looks up an item by the labels on the way from `root`, separated by `/`, e.g.
+/// `"Video/Gamma"`. Items that are currently borrowed are skipped.
+pub fn find_path(root: &Node, path: &str) -> Option<Node> {
+    let mut node = root.clone();
+
+    for label in path.split('/').filter(|label| !label.is_empty()) {
+        let next = node
+            .try_borrow()
+            .ok()?
+            .children()
+            .iter()
+            .find(|child| child.try_borrow().is_ok_and(|child| child.label() == label))
+            .map(undecorated);
+        node = next?;
+    }
+
+    Some(node)
+}
+
+/// Values of the items below `root` with the paths [find_path] resolves them by. Items that are
+/// currently borrowed are skipped.
+pub fn values(root: &Node) -> Vec<(String, Value)> {
+    collect_values(root, false)
+}
+
+/// Like [values], but keyed by `#` and the [MenuItem::id] for items that have one, so saved
+/// values survive renaming their labels. Resolved again by [persist::StateBlob::apply].
+pub(crate) fn keyed_values(root: &Node) -> Vec<(String, Value)> {
+    collect_values(root, true)
+}
+
+fn collect_values(root: &Node, by_id: bool) -> Vec<(String, Value)> {
+    fn walk(node: &Node, path: &str, by_id: bool, out: &mut Vec<(String, Value)>) {
+        let Ok(item) = node.try_borrow() else {
+            return;
+        };
+
+        for child in item.children() {
+            let Ok(c) = child.try_borrow() else {
+                continue;
+            };
+
+            let label = c.label();
+            if label.is_empty() {
+                continue;
+            }
+            let path = if path.is_empty() {
+                label
+            } else {
+                format!("{}/{}", path, label)
+            };
+
+            if let Some(value) = c.value() {
+                let key = match c.id().filter(|_| by_id) {
+                    Some(id) => format!("#{}", id),
+                    None => path.clone(),
+                };
+                out.push((key, value));
+            }
+
+            drop(c);
+            walk(&undecorated(child), &path, by_id, out);
         }
+    }
 
-        changed
+    let mut out = vec![];
+    walk(root, "", by_id, &mut out);
+    out
+}
+
+/// The item below `root` with the [MenuItem::id] `id`, or the item it decorates. Items that are
+/// currently borrowed are skipped.
+pub fn find(root: &Node, id: &str) -> Option<Node> {
+    let item = root.try_borrow().ok()?;
+    if item.id() == Some(id) {
+        return Some(undecorated(root));
+    }
+
+    item.children().iter().find_map(|child| find(child, id))
+}
+
+/// `node`, or the item it decorates, see [MenuItem::decorates].
+fn undecorated(node: &Node) -> Node {
+    let mut node = node.clone();
+    loop {
+        let inner = node.try_borrow().ok().and_then(|n| n.decorates().cloned());
+        match inner {
+            Some(inner) => node = inner,
+            None => return node,
+        }
     }
 }
 
 // endregion
 
-// region: Button
+// region: TypedNode
 
-pub struct Button {
-    text: String,
-    f: Box<dyn Fn()>,
+/// Widget whose state lives outside its node, so a [TypedNode] can read it without borrowing the
+/// node, e.g. from within the widget's own callback.
+pub trait Widget: MenuItem + 'static {
+    type State: Clone;
+
+    fn state(&self) -> Self::State;
 }
 
-impl Button {
-    pub fn new<F>(text: &str, f: F) -> Node
-    where
-        F: 'static + Fn(),
-    {
-        Rc::new(RefCell::new(Box::new(Self {
-            text: String::from(text),
-            f: Box::new(f),
-        })))
+/// A [Node] created holding a `W`, giving typed access to the widget.
+pub struct TypedNode<W: Widget> {
+    node: Node,
+    state: W::State,
+    widget: PhantomData<W>,
+}
+
+impl<W: Widget> TypedNode<W> {
+    fn new(widget: W) -> Self {
+        Self {
+            state: widget.state(),
+            node: Rc::new(RefCell::new(Box::new(widget))),
+            widget: PhantomData,
+        }
+    }
+
+    /// The erased node, for adding it to a [Menu].
+    pub fn node(&self) -> Node {
+        self.node.clone()
+    }
+
+    /// The widget, or `None` if the item in the node was replaced by one of another type.
+    pub fn try_borrow(&self) -> Option<Ref<'_, W>> {
+        Ref::filter_map(self.node.borrow(), |item| (**item).as_any().downcast_ref()).ok()
+    }
+
+    /// Like [TypedNode::try_borrow], mutably.
+    pub fn try_borrow_mut(&self) -> Option<RefMut<'_, W>> {
+        RefMut::filter_map(self.node.borrow_mut(), |item| {
+            (**item).as_any_mut().downcast_mut()
+        })
+        .ok()
+    }
+
+    /// The widget.
+    ///
+    /// # Panics
+    ///
+    /// If the item in the node was replaced by one of another type.
+    pub fn borrow(&self) -> Ref<'_, W> {
+        self.try_borrow().expect("node no longer holds its widget")
+    }
+
+    /// Like [TypedNode::borrow], mutably.
+    pub fn borrow_mut(&self) -> RefMut<'_, W> {
+        self.try_borrow_mut()
+            .expect("node no longer holds its widget")
+    }
+
+    pub fn value(&self) -> Option<Value> {
+        self.borrow().value()
+    }
+
+    pub fn set_value(&self, value: &Value) -> bool {
+        self.borrow_mut().set_value(value)
     }
 }
 
-impl MenuItem for Button {
-    fn render(&self) -> String {
-        format!("<{}>", self.text)
+impl<W: Widget> Clone for TypedNode<W> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+            state: self.state.clone(),
+            widget: PhantomData,
+        }
     }
+}
 
-    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
-        use wut::gamepad::Button as B;
-        if input.trigger.contains(B::A) {
-            (self.f)();
+impl<W: Widget> From<TypedNode<W>> for Node {
+    fn from(value: TypedNode<W>) -> Self {
+        value.node
+    }
+}
+
+// endregion
+
+// region: Template
+
+/// Stamps out independent copies of a widget definition.
+///
+/// Nodes share their state when the same [Node] is added twice, so repeated rows need to be built
+/// once per instance. `build` receives the instance number and the label with every `{n}`
+/// replaced by it.
+///
+/// ```ignore
+/// let slot = Template::new("Slot {n}", |n, label| {
+///     Button::new(label, move || save_state(n))
+/// });
+/// Menu::new("Savestates", slot.stamp(10))
+/// ```
+pub struct Template {
+    label: String,
+    build: Box<dyn Fn(usize, &str) -> Node>,
+}
+
+impl Template {
+    pub fn new<F>(label: &str, build: F) -> Self
+    where
+        F: 'static + Fn(usize, &str) -> Node,
+    {
+        Self {
+            label: String::from(label),
+            build: Box::new(build),
+        }
+    }
+
+    /// The instance numbered `n`.
+    pub fn instance(&self, n: usize) -> Node {
+        let label = self.label.replace("{n}", &format!("{}", n));
+        (self.build)(n, &label)
+    }
+
+    /// Instances `1..=count`.
+    pub fn stamp(&self, count: usize) -> Vec<Node> {
+        (1..=count).map(|n| self.instance(n)).collect()
+    }
+}
+
+// endregion
+
+// region: Derive
+
+#[cfg(feature = "derive")]
+pub use overlay_derive::{Options, OverlayMenu};
+
+// Paths used by the derived code, which cannot rely on the prelude of the deriving crate.
+#[doc(hidden)]
+pub use wut::alloc::{rc::Rc as __Rc, string::String as __String, vec as __vec, vec::Vec as __Vec};
+
+/// Menu with items bound to the fields of a settings struct, usually derived with
+/// `#[derive(OverlayMenu)]`. The items write changed values into the shared struct.
+pub trait OverlayMenu: Sized + 'static {
+    fn menu(name: &str, settings: Rc<RefCell<Self>>) -> Node;
+}
+
+/// Values offered by a [Select] for a field of an [OverlayMenu], usually derived with
+/// `#[derive(Options)]` for fieldless enums.
+pub trait Options: Sized + Clone + PartialEq + 'static {
+    /// Names and values in the order shown.
+    fn variants() -> Vec<(String, Self)>;
+}
+
+// endregion
+
+// region: Bytes
+
+/// Writes UTF-8 into a fixed buffer without allocating, cutting off whatever does not fit at a
+/// character boundary. Used by [MenuItem::render_bytes].
+#[cfg(feature = "byte-render")]
+pub struct ByteWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+#[cfg(feature = "byte-render")]
+impl<'a> ByteWriter<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// Bytes written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_str(&mut self, s: &str) -> &mut Self {
+        let free = self.buf.len() - self.len;
+        let mut end = s.len().min(free);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        self
+    }
+
+    pub fn push_u64(&mut self, mut value: u64) -> &mut Self {
+        let mut digits = [0u8; 20];
+        let mut i = digits.len();
+        loop {
+            i -= 1;
+            digits[i] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        // SAFETY: ASCII digits only.
+        self.push_str(unsafe { core::str::from_utf8_unchecked(&digits[i..]) })
+    }
+
+    pub fn push_i64(&mut self, value: i64) -> &mut Self {
+        if value < 0 {
+            self.push_str("-");
+        }
+        self.push_u64(value.unsigned_abs())
+    }
+}
+
+#[cfg(feature = "byte-render")]
+impl Write for ByteWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+// endregion
+
+// region: Menu
+
+pub struct Menu {
+    name: String,
+    items: Vec<Node>,
+    pos: usize,
+    focused: bool,
+    dirty: bool,
+    /// Entry last entered from this menu, see [MenuItem::owns].
+    entered: Option<Weak<RefCell<Box<dyn MenuItem>>>>,
+    repeat: timings::Repeat,
+    order: Option<(Rc<dyn persist::Storage>, String)>,
+    preview: bool,
+    /// Lines shown at once in list mode.
+    window: Option<usize>,
+    enabled: bool,
+    /// Builds the items each time the menu is entered.
+    populate: Option<Box<dyn Fn() -> Vec<Node>>>,
+    /// Query typed in the [MenuSearch] and the one the cursor was last placed for.
+    search: Option<(Rc<RefCell<String>>, String)>,
+}
+
+impl Menu {
+    pub fn new(name: &str, items: Vec<Node>) -> Node {
+        Rc::new(RefCell::new(Box::new(Self::create(name, items))))
+    }
+
+    /// Like [Menu::new], keeping a handle for changing the items at runtime, e.g. to list the
+    /// connected players. The handle must not be used while the menu handles input, e.g. from the
+    /// callback of one of its items.
+    pub fn typed(name: &str, items: Vec<Node>) -> TypedNode<Self> {
+        TypedNode::new(Self::create(name, items))
+    }
+
+    /// Like [Menu::new], but the user can move the selected item by holding Y and pressing
+    /// Up/Down. The order is saved to `storage` under `key` by item label and restored here.
+    pub fn reorderable(
+        name: &str,
+        mut items: Vec<Node>,
+        storage: Rc<dyn persist::Storage>,
+        key: &str,
+    ) -> Node {
+        if let Some(saved) = storage.load(key) {
+            let saved = String::from_utf8_lossy(&saved);
+            let labels: Vec<&str> = saved.lines().collect();
+            items.sort_by_key(|item| {
+                let label = item.borrow().label();
+                labels
+                    .iter()
+                    .position(|l| *l == label)
+                    .unwrap_or(usize::MAX)
+            });
+        }
+
+        let mut menu = Self::create(name, items);
+        menu.order = Some((storage, String::from(key)));
+        Rc::new(RefCell::new(Box::new(menu)))
+    }
+
+    fn create(name: &str, items: Vec<Node>) -> Self {
+        let mut menu = Self {
+            name: String::from(name),
+            items,
+            pos: 0,
+            focused: false,
+            dirty: false,
+            entered: None,
+            repeat: timings::Repeat::default(),
+            order: None,
+            preview: false,
+            window: None,
+            enabled: true,
+            populate: None,
+            search: None,
+        };
+        menu.pos = Self::first(&menu.entries());
+        menu
+    }
+
+    /// Like [Menu::new], but while open the menu lists up to `window` of its items, one per line
+    /// below its name, with the selected one marked by [theme::Theme::cursor]. Navigation is the
+    /// same as in a single line menu.
+    pub fn list(name: &str, window: usize, items: Vec<Node>) -> Node {
+        let mut menu = Self::create(name, items);
+        menu.window = Some(window.max(1));
+        Rc::new(RefCell::new(Box::new(menu)))
+    }
+
+    /// Writes the lines of list mode: the name with the position, then the window of entries
+    /// around the selected one.
+    fn render_list(&self, window: usize, out: &mut dyn Write) {
+        let entries = self.entries();
+        let _ = write!(out, "{} ({}/{})", self.name, self.pos + 1, entries.len());
+        Self::render_window(&entries, self.pos, window, out);
+    }
+
+    /// Writes up to `window` of `entries` around the one at `pos`, each on a line of its own
+    /// with the one at `pos` marked by [theme::Theme::cursor]. Entries left out above or below are
+    /// indicated on an extra line, so only the window is rendered however long the list is.
+    fn render_window(entries: &[Node], pos: usize, window: usize, out: &mut dyn Write) {
+        let theme = theme::current();
+        let indent = theme.cursor.chars().count();
+        let start = pos
+            .saturating_sub(window / 2)
+            .min(entries.len().saturating_sub(window));
+
+        if start > 0 {
+            let _ = write!(out, "\n{:2$}{}", "", theme.more_above, indent);
+        }
+        for (i, entry) in entries.iter().enumerate().skip(start).take(window) {
+            if i == pos {
+                let _ = write!(out, "\n{}", theme.cursor);
+            } else {
+                let _ = write!(out, "\n{:1$}", "", indent);
+            }
+            Self::render_entry(entry, out);
+        }
+        if start + window < entries.len() {
+            let _ = write!(out, "\n{:2$}{}", "", theme.more_below, indent);
+        }
+    }
+
+    /// Writes `entry`, marked if it is disabled or the [watchdog] disabled it.
+    fn render_entry(entry: &Node, out: &mut dyn Write) {
+        let item = entry.borrow();
+        item.render_into(out);
+        if !item.enabled() {
+            let _ = write!(out, " {}", theme::current().disabled);
+        }
+        if watchdog::is_faulted(entry) {
+            let _ = write!(out, " {}", theme::current().faulted);
+        }
+    }
+
+    /// Like [Menu::new], but Y opens a search narrowing the items to those whose label contains
+    /// the typed text, ignoring ASCII case. Items inside the menu do not get Y. The search is
+    /// cleared when the menu is left.
+    pub fn searchable(name: &str, items: Vec<Node>) -> Node {
+        let mut menu = Self::create(name, items);
+        menu.search = Some(Default::default());
+        Rc::new(RefCell::new(Box::new(menu)))
+    }
+
+    /// Menu whose items are built by `f` each time it is entered, e.g. for listing the files in
+    /// a directory. Until then it has no items, so paths do not resolve into it.
+    pub fn lazy<F>(name: &str, f: F) -> Node
+    where
+        F: 'static + Fn() -> Vec<Node>,
+    {
+        let mut menu = Self::create(name, vec![]);
+        menu.populate = Some(Box::new(f));
+        Rc::new(RefCell::new(Box::new(menu)))
+    }
+
+    /// Like [Menu::new], but while closed the menu shows the value of its selected item, e.g.
+    /// `Video (Gamma: 1.20)`.
+    pub fn previewing(name: &str, items: Vec<Node>) -> Node {
+        let mut menu = Self::create(name, items);
+        menu.preview = true;
+        Rc::new(RefCell::new(Box::new(menu)))
+    }
+
+    fn previewed(&self) -> Option<String> {
+        if !self.preview {
+            return None;
+        }
+        self.entry(self.pos)?.borrow().preview()
+    }
+
+    pub fn items(&self) -> &[Node] {
+        &self.items
+    }
+
+    /// Appends an item to the end of the menu.
+    pub fn push(&mut self, item: Node) {
+        self.mutate(|items| items.push(item));
+    }
+
+    /// Inserts an item at `index`, keeping the cursor on the currently selected item.
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, item: Node) {
+        self.mutate(|items| items.insert(index, item));
+    }
+
+    /// Removes the item at `index`.
+    ///
+    /// If the selected item is removed the cursor moves to its successor (or the new last item).
+    /// Navigation stack entries for removed items are popped by the [Overlay] on its next update.
+    pub fn remove(&mut self, index: usize) -> Option<Node> {
+        if index >= self.items.len() {
+            return None;
+        }
+
+        let mut removed = None;
+        self.mutate(|items| removed = Some(items.remove(index)));
+        removed
+    }
+
+    /// Removes all items.
+    pub fn clear(&mut self) {
+        self.mutate(|items| items.clear());
+    }
+
+    /// Navigable entries: the items, each followed by the entries it shows inline.
+    fn entries(&self) -> Vec<Node> {
+        fn walk(node: &Node, out: &mut Vec<Node>) {
+            out.push(node.clone());
+            let inline = node.borrow().inline();
+            for child in &inline {
+                walk(child, out);
+            }
+        }
+
+        let query = self.query();
+        let mut out = Vec::with_capacity(self.items.len());
+        for item in &self.items {
+            if Self::listed(item, query.as_deref()) {
+                walk(item, &mut out);
+            }
+        }
+        out
+    }
+
+    /// Entry at `index` of [Menu::entries], found without collecting them.
+    fn entry(&self, index: usize) -> Option<Node> {
+        fn walk(node: &Node, index: &mut usize) -> Option<Node> {
+            if *index == 0 {
+                return Some(node.clone());
+            }
+            *index -= 1;
+            let inline = node.borrow().inline();
+            inline.iter().find_map(|child| walk(child, index))
+        }
+
+        let query = self.query();
+        let mut index = index;
+        self.items
+            .iter()
+            .filter(|item| Self::listed(item, query.as_deref()))
+            .find_map(|item| walk(item, &mut index))
+    }
+
+    /// Lowercased search query, `None` unless one is typed.
+    fn query(&self) -> Option<String> {
+        let (query, _) = self.search.as_ref()?;
+        let query = query.borrow();
+        (!query.is_empty()).then(|| query.to_ascii_lowercase())
+    }
+
+    /// Whether `item` matches the lowercased search `query`.
+    fn listed(item: &Node, query: Option<&str>) -> bool {
+        query.is_none_or(|query| item.borrow().label().to_ascii_lowercase().contains(query))
+    }
+
+    /// Applies `f` to the items and moves the cursor back to the item it was on, or, if that
+    /// item is gone, keeps it in place within bounds.
+    fn mutate(&mut self, f: impl FnOnce(&mut Vec<Node>)) {
+        let selected = self.entry(self.pos);
+        f(&mut self.items);
+        let entries = self.entries();
+
+        match selected
+            .as_ref()
+            .and_then(|s| entries.iter().position(|e| Rc::ptr_eq(e, s)))
+        {
+            Some(pos) => self.pos = pos,
+            None => {
+                if let Some(selected) = selected {
+                    selected.borrow_mut().select(false);
+                }
+                self.pos = self.pos.min(entries.len().saturating_sub(1));
+                if !entries.get(self.pos).is_some_and(Self::stops) {
+                    self.pos = Self::step(&entries, self.pos, true);
+                }
+                if self.focused {
+                    self.select_current(true);
+                }
+            }
+        }
+
+        self.dirty = true;
+    }
+
+    /// Moves `item` one place up (towards the front) or down and saves the new order.
+    fn reorder(&mut self, item: &Node, up: bool) -> bool {
+        let Some(index) = self.items.iter().position(|i| Rc::ptr_eq(i, item)) else {
+            return false;
+        };
+        let target = if up {
+            index.checked_sub(1)
+        } else {
+            (index + 1 < self.items.len()).then_some(index + 1)
+        };
+        let Some(target) = target else {
+            return false;
+        };
+
+        self.mutate(|items| items.swap(index, target));
+
+        if let Some((storage, key)) = &self.order {
+            let mut order = String::new();
+            for item in &self.items {
+                order.push_str(&item.borrow().label());
+                order.push('\n');
+            }
+            let _ = storage.save(key, order.as_bytes());
+        }
+        true
+    }
+
+    fn select_current(&self, selected: bool) {
+        if let Some(item) = self.entries().get(self.pos) {
+            item.borrow_mut().select(selected);
+        }
+    }
+
+    /// Cursor movement for this frame (`true` is forward), repeating faster the longer
+    /// Left/Right is held as configured by the [timings] of `ctx`.
+    fn scroll(&mut self, input: &State, ctx: &MenuContext) -> Option<bool> {
+        use wut::gamepad::Button as B;
+        self.repeat
+            .step(input, B::Left, B::Right, &ctx.timings(), ctx.elapsed())
+    }
+
+    /// Whether the cursor stops on `entry`: it is enabled and [MenuItem::interactive].
+    fn stops(entry: &Node) -> bool {
+        entry
+            .try_borrow()
+            .is_ok_and(|entry| entry.enabled() && entry.interactive())
+    }
+
+    /// Position of the first entry the cursor stops on, or 0 if there is none.
+    fn first(entries: &[Node]) -> usize {
+        entries.iter().position(Self::stops).unwrap_or(0)
+    }
+
+    /// Position of the next entry after `pos` in the direction the cursor stops on, wrapping
+    /// around. `pos` itself is only returned if the cursor stops on no other entry.
+    fn step(entries: &[Node], pos: usize, forward: bool) -> usize {
+        let len = entries.len();
+        let mut next = pos;
+        for _ in 1..len {
+            next = if forward {
+                (next + 1) % len
+            } else {
+                (next + len - 1) % len
+            };
+            if Self::stops(&entries[next]) {
+                return next;
+            }
+        }
+        pos
+    }
+
+    fn leave(&mut self, stack: &mut Vec<Node>) -> bool {
+        if stack.len() > 1 {
+            self.blur();
+            stack.pop();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Query of a [Menu::searchable], pushed by Y. Up/Down picks a character, X appends it and Y
+/// deletes the last one. A keeps the query and B clears it.
+struct MenuSearch {
+    query: Rc<RefCell<String>>,
+    pick: usize,
+}
+
+impl MenuItem for MenuSearch {
+    fn render(&self) -> String {
+        let pick = match PALETTE_CHARS[self.pick] {
+            b' ' => '_',
+            c => c as char,
+        };
+        format!("Search: {}[{}]", self.query.borrow(), pick)
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        let t = input.trigger;
+
+        if t.contains(B::A) {
+            stack.pop();
+        } else if t.contains(B::B) {
+            self.query.borrow_mut().clear();
+            stack.pop();
+        } else if t.contains(B::Up) {
+            self.pick = (self.pick + 1) % PALETTE_CHARS.len();
+        } else if t.contains(B::Down) {
+            self.pick = (self.pick + PALETTE_CHARS.len() - 1) % PALETTE_CHARS.len();
+        } else if t.contains(B::X) {
+            self.query
+                .borrow_mut()
+                .push(PALETTE_CHARS[self.pick] as char);
+        } else if t.contains(B::Y) {
+            self.query.borrow_mut().pop();
+        } else {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl TypedNode<Menu> {
+    pub fn len(&self) -> usize {
+        self.borrow().items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.borrow().items.is_empty()
+    }
+
+    /// See [Menu::push].
+    pub fn push(&self, item: Node) {
+        self.borrow_mut().push(item);
+    }
+
+    /// See [Menu::insert].
+    pub fn insert(&self, index: usize, item: Node) {
+        self.borrow_mut().insert(index, item);
+    }
+
+    /// See [Menu::remove].
+    pub fn remove(&self, index: usize) -> Option<Node> {
+        self.borrow_mut().remove(index)
+    }
+
+    /// See [Menu::clear].
+    pub fn clear(&self) {
+        self.borrow_mut().clear();
+    }
+}
+
+impl Widget for Menu {
+    type State = ();
+
+    fn state(&self) -> Self::State {}
+}
+
+impl MenuItem for Menu {
+    fn focus(&mut self) {
+        if let Some(populate) = &self.populate {
+            self.items = populate();
+            self.pos = Self::first(&self.entries());
+        }
+        self.focused = true;
+        self.repeat.reset();
+        self.select_current(true);
+    }
+
+    fn blur(&mut self) {
+        self.focused = false;
+        self.select_current(false);
+        if let Some((query, _)) = &self.search {
+            query.borrow_mut().clear();
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    /// Only the entered entry is disowned, once it is no longer among the entries; nodes pushed
+    /// on top of the menu by other means, e.g. the [Palette], are left alone.
+    fn owns(&self, node: &Node) -> bool {
+        let entered = self
+            .entered
+            .as_ref()
+            .is_some_and(|entered| entered.ptr_eq(&Rc::downgrade(node)));
+        !entered || self.entries().iter().any(|entry| Rc::ptr_eq(entry, node))
+    }
+
+    fn children(&self) -> &[Node] {
+        &self.items
+    }
+
+    fn tick(&mut self) -> bool {
+        self.tick_ctx(&mut MenuContext::detached())
+    }
+
+    fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+        let mut searched = false;
+        if let Some((query, placed)) = &mut self.search
+            && *query.borrow() != *placed
+        {
+            placed.clone_from(&query.borrow());
+            searched = true;
+        }
+        if searched {
+            self.select_current(false);
+            self.pos = Self::first(&self.entries());
+            if self.focused {
+                self.select_current(true);
+            }
+        }
+
+        self.items.iter().fold(searched, |changed, item| {
+            watchdog::tick(item, ctx) | changed
+        })
+    }
+
+    fn summary(&self) -> String {
+        format!("{} {}", self.name, theme::current().submenu)
+    }
+
+    fn label(&self) -> String {
+        self.name.clone()
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn current(&self) -> Option<Node> {
+        self.entries().get(self.pos).cloned()
+    }
+
+    fn select_entry(&mut self, entry: &Node) -> bool {
+        let Some(pos) = self.entries().iter().position(|e| Rc::ptr_eq(e, entry)) else {
+            return false;
+        };
+
+        self.select_current(false);
+        self.pos = pos;
+        self.select_current(true);
+        // [Overlay::navigate] enters the entry next, if it can be entered.
+        self.entered = Some(Rc::downgrade(entry));
+        true
+    }
+
+    #[cfg(feature = "byte-render")]
+    fn render_bytes(&self, buf: &mut [u8]) -> usize {
+        let theme = theme::current();
+        let mut out = ByteWriter::new(buf);
+
+        if self.focused && self.window.is_some() {
+            return out.push_str(&rendered(self)).len();
+        }
+
+        if !self.focused {
+            out.push_str(&self.name).push_str(" ");
+            if let Some(preview) = self.previewed() {
+                out.push_str("(").push_str(&preview).push_str(") ");
+            }
+            return out.push_str(theme.submenu).len();
+        }
+
+        out.push_str(theme.prev)
+            .push_str(theme.gap)
+            .push_str(theme.item);
+        let mut len = out.len();
+        if let Some(item) = self.entry(self.pos) {
+            len += item.borrow().render_bytes(&mut buf[len..]);
+            if !item.borrow().enabled() {
+                len += ByteWriter::new(&mut buf[len..])
+                    .push_str(" ")
+                    .push_str(theme.disabled)
+                    .len();
+            }
+            if watchdog::is_faulted(&item) {
+                len += ByteWriter::new(&mut buf[len..])
+                    .push_str(" ")
+                    .push_str(theme.faulted)
+                    .len();
+            }
+        }
+
+        let mut out = ByteWriter::new(&mut buf[len..]);
+        len + out.push_str(theme.gap).push_str(theme.next).len()
+    }
+
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let theme = theme::current();
+        if !self.focused {
+            let _ = match self.previewed() {
+                Some(preview) => write!(out, "{} ({}) {}", self.name, preview, theme.submenu),
+                None => write!(out, "{} {}", self.name, theme.submenu),
+            };
+            return;
+        }
+
+        if let Some(window) = self.window {
+            self.render_list(window, out);
+            return;
+        }
+
+        let _ = write!(out, "{}{}{}", theme.prev, theme.gap, theme.item);
+        if let Some(item) = self.entries().get(self.pos) {
+            Self::render_entry(item, out);
+        }
+        let _ = write!(out, "{}{}", theme.gap, theme.next);
+    }
+
+    /// Puts the badge in front of the submenu marker while closed.
+    fn render_decorated(&self, badge: &str, out: &mut dyn Write) {
+        if self.focused {
+            return self.render_into(out);
+        }
+
+        let _ = write!(out, "{} ", self.name);
+        if let Some(preview) = self.previewed() {
+            let _ = write!(out, "({}) ", preview);
+        }
+        let _ = write!(out, "({}) {}", badge, theme::current().submenu);
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        self.control_ctx(input, stack, &mut MenuContext::detached())
+    }
+
+    fn control_ctx(&mut self, input: State, stack: &mut Vec<Node>, ctx: &mut MenuContext) -> bool {
+        use wut::gamepad::Button as B;
+        let mut changed = core::mem::take(&mut self.dirty);
+
+        if let Some((query, _)) = self.search.as_ref().filter(|_| self.order.is_none())
+            && input.trigger.contains(B::Y)
+        {
+            stack.push(Rc::new(RefCell::new(Box::new(MenuSearch {
+                query: query.clone(),
+                pick: 0,
+            }))));
+            return true;
+        }
+
+        let entries = self.entries();
+
+        let Some(item) = entries.get(self.pos).cloned() else {
+            if input.trigger.contains(B::B) {
+                changed |= self.leave(stack);
+            }
+            return changed;
+        };
+
+        let reorder = self.order.is_some() && input.hold.contains(B::Y);
+        let inert = watchdog::is_faulted(&item) || !item.borrow().enabled();
+
+        if reorder && (input.trigger.contains(B::Up) || input.trigger.contains(B::Down)) {
+            changed |= self.reorder(&item, input.trigger.contains(B::Up));
+        } else if !inert && item.borrow().enters(&input) {
+            item.borrow_mut().focus();
+            self.entered = Some(Rc::downgrade(&item));
+            stack.push(item);
+            changed = true;
+        } else if input.trigger.contains(B::B) {
+            changed |= self.leave(stack);
+        } else if let Some(forward) = self.scroll(&input, ctx) {
+            self.select_current(false);
+            self.pos = Self::step(&entries, self.pos, forward);
+            self.select_current(true);
+            changed = true;
+        } else if !inert {
+            changed |= item.borrow_mut().control_ctx(input, stack, ctx);
+        }
+
+        changed
+    }
+}
+
+// endregion
+
+// region: FilePicker
+
+/// Browses the filesystem below a directory, e.g. [persist::SD_ROOT], in nested menus read each
+/// time they are entered. Directories come first, both sorted by name; A on a file passes its
+/// path to the callback.
+pub struct FilePicker;
+
+impl FilePicker {
+    pub fn new<F>(text: &str, root: &str, f: F) -> Node
+    where
+        F: 'static + Fn(&str),
+    {
+        Self::new_ctx(text, root, move |path, _| f(path))
+    }
+
+    /// Like [FilePicker::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, root: &str, f: F) -> Node
+    where
+        F: 'static + Fn(&str, &mut MenuContext),
+    {
+        Self::directory(text, String::from(root.trim_end_matches('/')), Rc::new(f))
+    }
+
+    fn directory(text: &str, path: String, f: Rc<dyn Fn(&str, &mut MenuContext)>) -> Node {
+        Menu::lazy(text, move || {
+            let mut entries: Vec<(bool, String)> = wut::fs::read_dir(&path)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| {
+                    let dir = entry.file_type().is_ok_and(|t| t.is_dir());
+                    (!dir, String::from(entry.file_name()))
+                })
+                .collect();
+            entries.sort();
+
+            if entries.is_empty() {
+                return vec![Text::new(|| String::from("(empty)"))];
+            }
+
+            entries
+                .into_iter()
+                .map(|(file, name)| {
+                    let path = format!("{}/{}", path, name);
+                    if file {
+                        let f = f.clone();
+                        Button::new_ctx(&name, move |ctx| f(&path, ctx))
+                    } else {
+                        Self::directory(&name, path, f.clone())
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+// endregion
+
+// region: Section
+
+/// Header that shows or hides its items in place within the parent [Menu] when activated with A.
+pub struct Section {
+    name: String,
+    items: Vec<Node>,
+    expanded: bool,
+}
+
+impl Section {
+    pub fn new(name: &str, expanded: bool, items: Vec<Node>) -> Node {
+        Rc::new(RefCell::new(Box::new(Self {
+            name: String::from(name),
+            items,
+            expanded,
+        })))
+    }
+}
+
+impl MenuItem for Section {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let theme = theme::current();
+        let icon = if self.expanded {
+            theme.expanded
+        } else {
+            theme.collapsed
+        };
+
+        let _ = write!(out, "{} {}", icon, self.name);
+    }
+
+    #[cfg(feature = "byte-render")]
+    fn render_bytes(&self, buf: &mut [u8]) -> usize {
+        let theme = theme::current();
+        let icon = if self.expanded {
+            theme.expanded
+        } else {
+            theme.collapsed
+        };
+
+        ByteWriter::new(buf)
+            .push_str(icon)
+            .push_str(" ")
+            .push_str(&self.name)
+            .len()
+    }
+
+    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+
+        if input.trigger.contains(B::A) {
+            self.expanded = !self.expanded;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn inline(&self) -> Vec<Node> {
+        if self.expanded {
+            self.items.clone()
+        } else {
+            vec![]
+        }
+    }
+
+    fn label(&self) -> String {
+        self.name.clone()
+    }
+
+    fn children(&self) -> &[Node] {
+        &self.items
+    }
+
+    fn tick(&mut self) -> bool {
+        self.tick_ctx(&mut MenuContext::detached())
+    }
+
+    fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+        self.items
+            .iter()
+            .fold(false, |changed, item| watchdog::tick(item, ctx) | changed)
+    }
+}
+
+// endregion
+
+// region: Button
+
+pub struct Button {
+    text: Arc<str>,
+    enabled: bool,
+    f: Box<dyn Fn()>,
+    error: Failure,
+    cooldown: u32,
+    remaining: u32,
+}
+
+impl Button {
+    pub fn new<F>(text: &str, f: F) -> Node
+    where
+        F: 'static + Fn(),
+    {
+        Self::with_cooldown(text, 0, f)
+    }
+
+    /// Like [Button::new] with a callback that mutates its captures. The callback must not press
+    /// the button again.
+    pub fn new_mut<F>(text: &str, f: F) -> Node
+    where
+        F: 'static + FnMut(),
+    {
+        let f = RefCell::new(f);
+        Self::new(text, move || (f.borrow_mut())())
+    }
+
+    /// Like [Button::new], but presses within `cooldown_ms` milliseconds of the last accepted one
+    /// are ignored while the remaining time is shown.
+    pub fn with_cooldown<F>(text: &str, cooldown_ms: u32, f: F) -> Node
+    where
+        F: 'static + Fn(),
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            f: Box::new(f),
+            error: Failure::default(),
+            cooldown: (cooldown_ms * FRAMES_PER_SECOND).div_ceil(1000),
+            remaining: 0,
+        })))
+    }
+
+    /// Like [Button::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, f: F) -> Node
+    where
+        F: 'static + Fn(&mut MenuContext),
+    {
+        Contextual::new(|slot| Self::new(text, move || slot.with(&f)))
+    }
+
+    fn press(&mut self) {
+        if self.remaining == 0 {
+            self.remaining = self.cooldown;
+            (self.f)();
+        }
+    }
+
+    /// Remaining cooldown in tenths of a second.
+    fn remaining_tenths(&self) -> u32 {
+        (self.remaining * 10).div_ceil(FRAMES_PER_SECOND)
+    }
+
+    /// Like [Button::new] with a fallible callback.
+    pub fn try_new<F>(text: &str, f: F) -> Node
+    where
+        F: 'static + Fn() -> Result<(), String>,
+    {
+        let error = Failure::default();
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            f: Box::new({
+                let error = error.clone();
+                move || error.record(f())
+            }),
+            error,
+            cooldown: 0,
+            remaining: 0,
+        })))
+    }
+}
+
+impl MenuItem for Button {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let _ = write!(out, "<{}>", self.text);
+        if self.remaining > 0 {
+            let tenths = self.remaining_tenths();
+            let _ = write!(out, " ({}.{}s)", tenths / 10, tenths % 10);
+        }
+    }
+
+    #[cfg(feature = "byte-render")]
+    fn render_bytes(&self, buf: &mut [u8]) -> usize {
+        let mut out = ByteWriter::new(buf);
+        out.push_str("<").push_str(&self.text).push_str(">");
+        if self.remaining > 0 {
+            let tenths = self.remaining_tenths() as u64;
+            out.push_str(" (")
+                .push_u64(tenths / 10)
+                .push_str(".")
+                .push_u64(tenths % 10)
+                .push_str("s)");
+        }
+        out.len()
+    }
+
+    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        if input.trigger.contains(B::A) {
+            let cooling = self.remaining > 0;
+            self.press();
+            return !cooling && self.remaining > 0;
+        }
+        false
+    }
+
+    fn tick(&mut self) -> bool {
+        self.tick_ctx(&mut MenuContext::detached())
+    }
+
+    fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+
+        let before = self.remaining_tenths();
+        self.remaining = self.remaining.saturating_sub(ctx.elapsed());
+        before != self.remaining_tenths()
+    }
+
+    fn actionable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self) {
+        self.press();
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn take_error(&mut self) -> Option<String> {
+        self.error.take()
+    }
+}
+
+// endregion
+
+// region: Confirm
+
+/// Button for destructive actions: A asks "Are you sure?" on a node pushed onto the navigation
+/// stack, where A invokes the callback and B cancels.
+pub struct Confirm {
+    text: Arc<str>,
+    enabled: bool,
+    question: String,
+    f: Rc<dyn Fn()>,
+}
+
+impl Confirm {
+    pub fn new<F>(text: &str, f: F) -> Node
+    where
+        F: 'static + Fn(),
+    {
+        Self::with_question(text, "Are you sure?", f)
+    }
+
+    /// Like [Confirm::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, f: F) -> Node
+    where
+        F: 'static + Fn(&mut MenuContext),
+    {
+        Contextual::new(|slot| Self::new(text, move || slot.with(&f)))
+    }
+
+    /// Like [Confirm::new], asking `question` instead.
+    pub fn with_question<F>(text: &str, question: &str, f: F) -> Node
+    where
+        F: 'static + Fn(),
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            question: String::from(question),
+            f: Rc::new(f),
+        })))
+    }
+}
+
+impl MenuItem for Confirm {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let _ = write!(out, "<{}>", self.text);
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        if !input.trigger.contains(B::A) {
+            return false;
+        }
+
+        stack.push(Rc::new(RefCell::new(Box::new(ConfirmPrompt {
+            question: self.question.clone(),
+            f: self.f.clone(),
+        }))));
+        true
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+}
+
+/// Question pushed by a [Confirm].
+struct ConfirmPrompt {
+    question: String,
+    f: Rc<dyn Fn()>,
+}
+
+impl MenuItem for ConfirmPrompt {
+    fn render(&self) -> String {
+        format!("{} A=Yes B=No", self.question)
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        if input.trigger.contains(B::A) {
+            stack.pop();
+            (self.f)();
+        } else if input.trigger.contains(B::B) {
+            stack.pop();
+        } else {
+            return false;
+        }
+
+        true
+    }
+}
+
+// endregion
+
+// region: Wizard
+
+/// Guided sequence of pages, e.g. for first-run configuration. A opens the wizard on its first
+/// page. Right moves to the next page and, on the last one, finishes: the wizard closes and
+/// passes the labels and values of its pages to the callback. Left moves back and B cancels; any
+/// other input goes to the page.
+pub struct Wizard {
+    text: Arc<str>,
+    enabled: bool,
+    pages: Vec<Node>,
+    page: usize,
+    open: bool,
+    f: Box<dyn Fn(&[(String, Value)])>,
+}
+
+impl Wizard {
+    pub fn new<F>(text: &str, pages: Vec<Node>, f: F) -> Node
+    where
+        F: 'static + Fn(&[(String, Value)]),
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            pages,
+            page: 0,
+            open: false,
+            f: Box::new(f),
+        })))
+    }
+
+    /// Like [Wizard::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, pages: Vec<Node>, f: F) -> Node
+    where
+        F: 'static + Fn(&[(String, Value)], &mut MenuContext),
+    {
+        Contextual::new(|slot| {
+            Self::new(text, pages, move |values| slot.with(|ctx| f(values, ctx)))
+        })
+    }
+
+    /// Values of the pages by label, followed by those of their children as with [values].
+    fn collect(&self) -> Vec<(String, Value)> {
+        let mut out = vec![];
+        for page in &self.pages {
+            let Ok(p) = page.try_borrow() else {
+                continue;
+            };
+
+            let label = p.label();
+            if let Some(value) = p.value() {
+                out.push((label.clone(), value));
+            }
+            drop(p);
+
+            for (path, value) in values(page) {
+                let path = if label.is_empty() {
+                    path
+                } else {
+                    format!("{}/{}", label, path)
+                };
+                out.push((path, value));
+            }
+        }
+        out
+    }
+}
+
+impl MenuItem for Wizard {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let theme = theme::current();
+        if !self.open {
+            let _ = write!(out, "{} {}", self.text, theme.submenu);
+            return;
+        }
+
+        let Some(page) = self.pages.get(self.page) else {
+            let _ = write!(out, "{}: no pages", self.text);
+            return;
+        };
+
+        let last = self.page + 1 == self.pages.len();
+        let _ = write!(
+            out,
+            "{} [{}/{}] ",
+            self.text,
+            self.page + 1,
+            self.pages.len()
+        );
+        match page.try_borrow() {
+            Ok(page) => page.render_into(out),
+            Err(_) => {
+                let _ = out.write_str("...");
+            }
+        }
+        let _ = write!(
+            out,
+            "{}{}",
+            theme.gap,
+            if last { "Right=Finish" } else { "Right=Next" }
+        );
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        self.control_ctx(input, stack, &mut MenuContext::detached())
+    }
+
+    fn control_ctx(&mut self, input: State, stack: &mut Vec<Node>, ctx: &mut MenuContext) -> bool {
+        use wut::gamepad::Button as B;
+
+        if !self.open {
+            return false;
+        }
+
+        if input.trigger.contains(B::B) {
+            self.blur();
+            stack.pop();
+        } else if input.trigger.contains(B::Right) {
+            if self.page + 1 < self.pages.len() {
+                self.page += 1;
+            } else {
+                let values = self.collect();
+                self.blur();
+                stack.pop();
+                (self.f)(&values);
+            }
+        } else if input.trigger.contains(B::Left) {
+            self.page = self.page.saturating_sub(1);
+        } else {
+            return self
+                .pages
+                .get(self.page)
+                .is_some_and(|page| page.borrow_mut().control_ctx(input, stack, ctx));
+        }
+
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        self.tick_ctx(&mut MenuContext::detached())
+    }
+
+    fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+        let mut changed = false;
+        for page in &self.pages {
+            changed |= watchdog::tick(page, ctx);
+        }
+        changed && self.open
+    }
+
+    fn focus(&mut self) {
+        self.open = true;
+        self.page = 0;
+    }
+
+    fn blur(&mut self) {
+        self.open = false;
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn children(&self) -> &[Node] {
+        &self.pages
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+}
+
+// endregion
+
+// region: Text
+
+pub struct Text {
+    f: Box<dyn Fn() -> String>,
+}
+
+impl Text {
+    pub fn new<F>(f: F) -> Node
+    where
+        F: 'static + Fn() -> String,
+    {
+        Rc::new(RefCell::new(Box::new(Self { f: Box::new(f) })))
+    }
+
+    /// Text filled in from the items below `root` on every render. Each `{...}` in `template` is
+    /// replaced by an item path as resolved by [find_path], a number, or operands like these
+    /// joined by `+`, `-`, `*` and `/` with spaces around them, e.g.
+    /// `"{Speed} * {Multiplier} = {Speed * Multiplier}"`. `*` and `/` bind tighter. A lone path
+    /// shows the item's [Value]; unknown paths and non-numeric operands show `?`.
+    pub fn formula(root: &Node, template: &str) -> Node {
+        let root = Rc::downgrade(root);
+        let template = String::from(template);
+        Self::new(move || match root.upgrade() {
+            Some(root) => Self::fill(&root, &template),
+            None => template.clone(),
+        })
+    }
+
+    fn fill(root: &Node, template: &str) -> String {
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}') else {
+                break;
+            };
+            out.push_str(&rest[..start]);
+            match Self::evaluate(root, &rest[start + 1..start + end]) {
+                Some(value) => {
+                    let _ = write!(out, "{}", value);
+                }
+                None => out.push('?'),
+            }
+            rest = &rest[start + end + 1..];
+        }
+
+        out.push_str(rest);
+        out
+    }
+
+    fn evaluate(root: &Node, expression: &str) -> Option<Value> {
+        let operand = |words: &[&str]| -> Option<Value> {
+            let operand = words.join(" ");
+            if let Ok(v) = operand.parse::<i64>() {
+                return Some(Value::Int(v));
+            }
+            if let Ok(v) = operand.parse::<f64>() {
+                return Some(Value::Float(v));
+            }
+            let node = find_path(root, &operand)?;
+            node.try_borrow().ok()?.value()
+        };
+        let number = |value: Value| match value {
+            Value::Int(v) => Some((v as f64, true)),
+            Value::Float(v) => Some((v, false)),
+            Value::Index(i) => Some((i as f64, true)),
+            Value::Bool(b) => Some((b as u8 as f64, true)),
+            Value::Text(_) => None,
+        };
+
+        let mut operands = vec![];
+        let mut operators = vec![];
+        let mut words = vec![];
+        for word in expression.split_whitespace() {
+            if matches!(word, "+" | "-" | "*" | "/") {
+                operands.push(operand(&words)?);
+                operators.push(word);
+                words.clear();
+            } else {
+                words.push(word);
+            }
+        }
+        operands.push(operand(&words)?);
+
+        if operators.is_empty() {
+            return operands.pop();
+        }
+
+        let mut integral = true;
+        let mut numbers = vec![];
+        for value in operands {
+            let (v, int) = number(value)?;
+            integral &= int;
+            numbers.push(v);
+        }
+
+        // Products first, then the sum of the terms.
+        let mut terms = vec![(1.0, numbers[0])];
+        for (operator, v) in operators.iter().zip(&numbers[1..]) {
+            let term = terms.last_mut()?;
+            match *operator {
+                "*" => term.1 *= v,
+                "/" if *v == 0.0 => return None,
+                "/" => {
+                    term.1 /= v;
+                    integral = false;
+                }
+                "+" => terms.push((1.0, *v)),
+                _ => terms.push((-1.0, *v)),
+            }
+        }
+        let result: f64 = terms.iter().map(|(sign, v)| sign * v).sum();
+
+        Some(if integral {
+            Value::Int(result as i64)
+        } else {
+            Value::Float(result)
+        })
+    }
+}
+
+impl MenuItem for Text {
+    fn render(&self) -> String {
+        format!("{}", (self.f)())
+    }
+
+    fn control(&mut self, _input: State, _stack: &mut Vec<Node>) -> bool {
+        true
+    }
+
+    fn interactive(&self) -> bool {
+        false
+    }
+}
+
+// endregion
+
+// region: Separator
+
+/// Length of a [Separator] in [theme::Theme::rule]s.
+const SEPARATOR_WIDTH: usize = 12;
+
+/// Horizontal rule grouping the items of a menu. The cursor skips it.
+pub struct Separator;
+
+impl Separator {
+    pub fn new() -> Node {
+        Rc::new(RefCell::new(Box::new(Self)))
+    }
+}
+
+impl MenuItem for Separator {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let rule = theme::current().rule;
+        for _ in 0..SEPARATOR_WIDTH {
+            let _ = out.write_str(rule);
+        }
+    }
+
+    fn control(&mut self, _input: State, _stack: &mut Vec<Node>) -> bool {
+        false
+    }
+
+    fn interactive(&self) -> bool {
+        false
+    }
+}
+
+/// Static caption, e.g. heading a group of items. The cursor skips it.
+pub struct Label {
+    text: Arc<str>,
+}
+
+impl Label {
+    pub fn new(text: &str) -> Node {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+        })))
+    }
+}
+
+impl MenuItem for Label {
+    fn render(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let _ = out.write_str(&self.text);
+    }
+
+    fn control(&mut self, _input: State, _stack: &mut Vec<Node>) -> bool {
+        false
+    }
+
+    fn interactive(&self) -> bool {
+        false
+    }
+}
+
+// endregion
+
+// region: Progress
+
+/// Read-only bar showing the fraction (from 0 to 1) and text returned by `f`, e.g.
+/// `Loading [====------]`. `f` is polled each tick.
+pub struct Progress {
+    f: Box<dyn Fn() -> (f32, String)>,
+    /// Filled segments and text when last polled.
+    shown: (u32, String),
+}
+
+impl Progress {
+    pub fn new<F>(f: F) -> Node
+    where
+        F: 'static + Fn() -> (f32, String),
+    {
+        let shown = Self::poll(&f);
+        Rc::new(RefCell::new(Box::new(Self {
+            f: Box::new(f),
+            shown,
+        })))
+    }
+
+    fn poll(f: &dyn Fn() -> (f32, String)) -> (u32, String) {
+        const SEGMENTS: f32 = PROGRESS_SEGMENTS as f32;
+        let (fraction, text) = f();
+        ((fraction.clamp(0.0, 1.0) * SEGMENTS) as u32, text)
+    }
+}
+
+const PROGRESS_SEGMENTS: u32 = 10;
+
+impl MenuItem for Progress {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let (filled, text) = &self.shown;
+        let _ = write!(out, "{} ", text);
+        bar(out, *filled, PROGRESS_SEGMENTS);
+    }
+
+    fn control(&mut self, _input: State, _stack: &mut Vec<Node>) -> bool {
+        false
+    }
+
+    fn interactive(&self) -> bool {
+        false
+    }
+
+    fn tick(&mut self) -> bool {
+        let shown = Self::poll(&*self.f);
+        let changed = shown != self.shown;
+        self.shown = shown;
+        changed
+    }
+}
+
+// endregion
+
+// region: Readout
+
+/// Like [Text], but only re-evaluates `f` every `interval_ms` milliseconds, whether the overlay is
+/// open or not.
+pub struct Readout {
+    f: Box<dyn Fn() -> String>,
+    text: String,
+    interval: u32,
+    elapsed: u32,
+}
+
+impl Readout {
+    pub fn new<F>(interval_ms: u32, f: F) -> Node
+    where
+        F: 'static + Fn() -> String,
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: f(),
+            f: Box::new(f),
+            interval: (interval_ms * FRAMES_PER_SECOND).div_ceil(1000).max(1),
+            elapsed: 0,
+        })))
+    }
+}
+
+impl MenuItem for Readout {
+    fn render(&self) -> String {
+        self.text.clone()
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let _ = out.write_str(&self.text);
+    }
+
+    fn control(&mut self, _input: State, _stack: &mut Vec<Node>) -> bool {
+        false
+    }
+
+    fn interactive(&self) -> bool {
+        false
+    }
+
+    fn tick(&mut self) -> bool {
+        self.tick_ctx(&mut MenuContext::detached())
+    }
+
+    fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+        self.elapsed = self.elapsed.saturating_add(ctx.elapsed());
+        if self.elapsed < self.interval {
+            return false;
+        }
+
+        self.elapsed = 0;
+        let text = (self.f)();
+        let changed = text != self.text;
+        self.text = text;
+        changed
+    }
+}
+
+// endregion
+
+// region: UnitValue
+
+/// A display unit for [UnitValue]: the raw value is multiplied by `scale` and shown with
+/// `precision` decimals followed by `suffix`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unit {
+    pub suffix: &'static str,
+    pub scale: f64,
+    pub precision: usize,
+}
+
+impl Unit {
+    pub const fn new(suffix: &'static str, scale: f64, precision: usize) -> Self {
+        Self {
+            suffix,
+            scale,
+            precision,
+        }
+    }
+
+    /// Raw value in m/s.
+    pub const SPEED: [Unit; 2] = [Unit::new("m/s", 1.0, 2), Unit::new("km/h", 3.6, 1)];
+    /// Raw value in frames at 60 fps.
+    pub const FRAMES: [Unit; 2] = [
+        Unit::new("f", 1.0, 0),
+        Unit::new("s", 1.0 / FRAMES_PER_SECOND as f64, 3),
+    ];
+    /// Raw value in bytes.
+    pub const BYTES: [Unit; 2] = [
+        Unit::new("B", 1.0, 0),
+        Unit::new("MiB", 1.0 / (1024.0 * 1024.0), 2),
+    ];
+}
+
+/// Read-only value shown in one of several units, cycled with X while selected. It is drawn
+/// again whenever the value changes, but being a readout it has no [MenuItem::value].
+pub struct UnitValue {
+    text: Arc<str>,
+    enabled: bool,
+    units: Vec<Unit>,
+    unit: usize,
+    f: Box<dyn Fn() -> f64>,
+    /// Bits of the value when last ticked.
+    shown: u64,
+}
+
+impl UnitValue {
+    pub fn new<F>(text: &str, units: &[Unit], f: F) -> Node
+    where
+        F: 'static + Fn() -> f64,
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            units: units.to_vec(),
+            unit: 0,
+            shown: f().to_bits(),
+            f: Box::new(f),
+        })))
+    }
+}
+
+impl MenuItem for UnitValue {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let value = (self.f)();
+        let _ = match self.units.get(self.unit) {
+            Some(unit) => write!(
+                out,
+                "{}: {:.*} {}",
+                self.text,
+                unit.precision,
+                value * unit.scale,
+                unit.suffix
+            ),
+            None => write!(out, "{}: {}", self.text, value),
+        };
+    }
+
+    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+
+        if !input.trigger.contains(B::X) || self.units.len() < 2 {
+            return false;
+        }
+
+        self.unit = (self.unit + 1) % self.units.len();
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        let shown = (self.f)().to_bits();
+        core::mem::replace(&mut self.shown, shown) != shown
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+}
+
+// endregion
+
+// region: Debounce
+
+/// Delays a widget's callback until its value has been left alone for a while.
+struct Debounce {
+    frames: u32,
+    remaining: u32,
+}
+
+impl Debounce {
+    fn new(ms: u32) -> Self {
+        Self {
+            frames: (ms * FRAMES_PER_SECOND).div_ceil(1000).max(1),
+            remaining: 0,
+        }
+    }
+
+    /// Starts waiting again after the value changed.
+    fn restart(&mut self) {
+        self.remaining = self.frames;
+    }
+
+    fn cancel(&mut self) {
+        self.remaining = 0;
+    }
+
+    /// Whether the value has just become stable after `elapsed` more frames.
+    fn tick(&mut self, elapsed: u32) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+
+        self.remaining = self.remaining.saturating_sub(elapsed);
+        self.remaining == 0
+    }
+}
+
+// endregion
+
+// region: Number
+
+/// Numeric types [Number] can interpolate between.
+pub trait Lerp {
+    fn lerp(from: &Self, to: &Self, t: f32) -> Self;
+}
+
+macro_rules! impl_lerp {
+    ($($t:ty),*) => {
+        $(
+            impl Lerp for $t {
+                fn lerp(from: &Self, to: &Self, t: f32) -> Self {
+                    let (from, to) = (*from as f64, *to as f64);
+                    (from + (to - from) * t as f64) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_lerp!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+struct Ramp<T> {
+    lerp: fn(&T, &T, f32) -> T,
+    frames: u32,
+    frame: u32,
+    from: T,
+    to: T,
+}
+
+/// Larger steps of a [Number], see [TypedNode::with_step_scale].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepScale {
+    /// Every step moves by the increment.
+    Off,
+    /// Holding ZL moves by 10 increments, holding ZR by 100.
+    Modifier,
+    /// Steps in the same direction in quick succession, pressed or repeated, grow from 1 to 10
+    /// increments after 10 steps and to 100 after 20. A pause longer than
+    /// [timings::Timings::accelerate_pause] starts over.
+    Accelerate,
+}
+
+pub struct Number<T: Display + core::ops::AddAssign + core::ops::SubAssign + PartialOrd + Clone> {
+    text: Arc<str>,
+    enabled: bool,
+    value: Rc<RefCell<T>>,
+    inc: T,
+    min: T,
+    max: T,
+    f: Box<dyn Fn(&T)>,
+    applied: T,
+    ramp: Option<Ramp<T>>,
+    default: T,
+    error: Failure,
+    /// Value being edited digit by digit and the selected digit, counted from the right.
+    digits: Option<(i64, u32)>,
+    debounce: Option<Debounce>,
+    history: Option<Rc<History>>,
+    /// Hold-to-repeat of Up/Down, `None` if turned off.
+    repeat: Option<timings::Repeat>,
+    /// Timings of the repeat, the overlay's if `None`.
+    repeat_timings: Option<timings::Timings>,
+    scale: StepScale,
+    /// Steps in the current direction and frames since the last one, for [StepScale::Accelerate].
+    streak: (Option<bool>, u32, u32),
+    /// Shared value followed each tick, see [TypedNode::following].
+    source: Option<Box<dyn Fn() -> T>>,
+    codec: Codec<T>,
+}
+
+/// Conversion of a [Number] value from and to [Value], taken from [NumericValue] by the
+/// constructors so the item itself does without the bound.
+struct Codec<T> {
+    to: fn(&T) -> Value,
+    from: fn(&Value) -> Option<T>,
+}
+
+impl<T: NumericValue> Codec<T> {
+    fn new() -> Self {
+        Self {
+            to: T::to_value,
+            from: T::from_value,
+        }
+    }
+}
+
+impl<
+    T: 'static
+        + Display
+        + core::ops::AddAssign
+        + core::ops::SubAssign
+        + PartialOrd
+        + Clone
+        + NumericValue,
+> Number<T>
+{
+    pub fn new<F>(text: &str, value: T, inc: T, min: T, max: T, f: F) -> Node
+    where
+        F: 'static + Fn(&T),
+    {
+        Self::typed(text, value, inc, min, max, f).into()
+    }
+
+    /// Like [Number::new] with a callback that mutates its captures. The callback must not set
+    /// the number again.
+    pub fn new_mut<F>(text: &str, value: T, inc: T, min: T, max: T, f: F) -> Node
+    where
+        F: 'static + FnMut(&T),
+    {
+        let f = RefCell::new(f);
+        Self::new(text, value, inc, min, max, move |value| {
+            (f.borrow_mut())(value)
+        })
+    }
+
+    /// Like [Number::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, value: T, inc: T, min: T, max: T, f: F) -> Node
+    where
+        F: 'static + Fn(&T, &mut MenuContext),
+    {
+        Contextual::new(|slot| {
+            Self::new(text, value, inc, min, max, move |value| {
+                slot.with(|ctx| f(value, ctx))
+            })
+        })
+    }
+
+    pub fn typed<F>(text: &str, value: T, inc: T, min: T, max: T, f: F) -> TypedNode<Self>
+    where
+        F: 'static + Fn(&T),
+    {
+        TypedNode::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            applied: value.clone(),
+            default: value.clone(),
+            value: Rc::new(RefCell::new(value)),
+            inc,
+            min,
+            max,
+            f: Box::new(f),
+            ramp: None,
+            error: Failure::default(),
+            digits: None,
+            debounce: None,
+            history: None,
+            repeat: Some(timings::Repeat::default()),
+            repeat_timings: None,
+            scale: StepScale::Off,
+            streak: (None, 0, 0),
+            source: None,
+            codec: Codec::new(),
+        })
+    }
+
+    /// Like [Number::new], but Up/Down also apply the value once it has been left alone for
+    /// `debounce_ms` milliseconds. The shown value still changes immediately.
+    pub fn debounced<F>(
+        text: &str,
+        value: T,
+        inc: T,
+        min: T,
+        max: T,
+        debounce_ms: u32,
+        f: F,
+    ) -> Node
+    where
+        F: 'static + Fn(&T),
+    {
+        let number = Self::typed(text, value, inc, min, max, f);
+        number.borrow_mut().debounce = Some(Debounce::new(debounce_ms));
+        number.node()
+    }
+
+    /// Like [Number::new] with a fallible callback.
+    pub fn try_new<F>(text: &str, value: T, inc: T, min: T, max: T, f: F) -> Node
+    where
+        F: 'static + Fn(&T) -> Result<(), String>,
+    {
+        let error = Failure::default();
+        let number = Self::typed(text, value, inc, min, max, {
+            let error = error.clone();
+            move |value: &T| error.record(f(value))
+        });
+        number.borrow_mut().error = error;
+        number.node()
+    }
+
+    /// Like [Number::new], but applying a value with A feeds the callback values ramping from the
+    /// previously applied one to the new one, one per frame over `frames` frames.
+    pub fn smooth<F>(text: &str, value: T, inc: T, min: T, max: T, frames: u32, f: F) -> Node
+    where
+        T: Lerp,
+        F: 'static + Fn(&T),
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            applied: value.clone(),
+            ramp: Some(Ramp {
+                lerp: T::lerp,
+                frames: frames.max(1),
+                frame: frames.max(1),
+                from: value.clone(),
+                to: value.clone(),
+            }),
+            default: value.clone(),
+            value: Rc::new(RefCell::new(value)),
+            inc,
+            min,
+            max,
+            f: Box::new(f),
+            error: Failure::default(),
+            digits: None,
+            debounce: None,
+            history: None,
+            repeat: Some(timings::Repeat::default()),
+            repeat_timings: None,
+            scale: StepScale::Off,
+            streak: (None, 0, 0),
+            source: None,
+            codec: Codec::new(),
+        })))
+    }
+}
+
+impl Number<u32> {
+    /// Number kept in sync with `shared` both ways: changes are stored into it and values stored
+    /// by others are shown, so game code can read the setting directly.
+    pub fn bind(
+        text: &str,
+        shared: Arc<AtomicU32>,
+        inc: u32,
+        min: u32,
+        max: u32,
+    ) -> TypedNode<Self> {
+        let value = shared.load(Ordering::Relaxed).max(min).min(max);
+        Self::typed(text, value, inc, min, max, {
+            let shared = shared.clone();
+            move |value| shared.store(*value, Ordering::Relaxed)
+        })
+        .following(move || shared.load(Ordering::Relaxed))
+    }
+}
+
+impl Number<i32> {
+    /// Like `bind` of `Number<u32>`, for signed values.
+    pub fn bind(
+        text: &str,
+        shared: Arc<AtomicI32>,
+        inc: i32,
+        min: i32,
+        max: i32,
+    ) -> TypedNode<Self> {
+        let value = shared.load(Ordering::Relaxed).max(min).min(max);
+        Self::typed(text, value, inc, min, max, {
+            let shared = shared.clone();
+            move |value| shared.store(*value, Ordering::Relaxed)
+        })
+        .following(move || shared.load(Ordering::Relaxed))
+    }
+}
+
+impl<
+    T: 'static
+        + Display
+        + core::ops::AddAssign
+        + core::ops::SubAssign
+        + PartialOrd
+        + Clone
+        + NumericValue,
+> TypedNode<Number<T>>
+{
+    pub fn get(&self) -> T {
+        self.state.borrow().clone()
+    }
+
+    /// Sets the value, clamped to the range, and applies it.
+    pub fn set(&self, value: T) {
+        self.set_value(&value.to_value());
+    }
+
+    /// Timings of repeating Up/Down while held, `None` turning repeating off. Defaults to those of
+    /// the overlay, see [Overlay::set_timings].
+    pub fn with_repeat(self, timings: Option<timings::Timings>) -> Self {
+        let mut number = self.borrow_mut();
+        match timings {
+            Some(timings) => number.repeat_timings = Some(timings),
+            None => number.repeat = None,
+        }
+        drop(number);
+        self
+    }
+
+    /// Makes Up/Down move by multiples of the increment, see [StepScale].
+    pub fn with_step_scale(self, scale: StepScale) -> Self {
+        self.borrow_mut().scale = scale;
+        self
+    }
+
+    /// Shows the value returned by `source`, clamped to the range, whenever it differs from the
+    /// applied one, e.g. a setting also changed by the game. The callback is not invoked for it.
+    pub fn following<F>(self, source: F) -> Self
+    where
+        F: 'static + Fn() -> T,
+    {
+        self.borrow_mut().source = Some(Box::new(source));
+        self
+    }
+
+    /// Keeps the last `capacity` applied values. Y shows them as a sparkline, where Left/Right
+    /// pick one, A applies it again and B closes the view. The previous value is picked first.
+    pub fn with_history(self, capacity: usize) -> Self {
+        let history = History::new(capacity);
+        history.record(self.get().to_value());
+        self.borrow_mut().history = Some(Rc::new(history));
+        self
+    }
+}
+
+impl<T: Display + core::ops::AddAssign + core::ops::SubAssign + PartialOrd + Clone> Number<T> {
+    fn apply(&mut self) {
+        if let Some(debounce) = &mut self.debounce {
+            debounce.cancel();
+        }
+        if let Some(history) = &self.history {
+            history.record((self.codec.to)(&self.value.borrow()));
+        }
+
+        match &mut self.ramp {
+            Some(ramp) => {
+                ramp.from = self.applied.clone();
+                ramp.to = self.value.borrow().clone();
+                ramp.frame = 0;
+            }
+            None => {
+                self.applied = self.value.borrow().clone();
+                (self.f)(&self.applied);
+            }
+        }
+    }
+}
+
+impl<T: 'static + Display + core::ops::AddAssign + core::ops::SubAssign + PartialOrd + Clone> Widget
+    for Number<T>
+{
+    type State = Rc<RefCell<T>>;
+
+    fn state(&self) -> Self::State {
+        self.value.clone()
+    }
+}
+
+impl<T: Display + core::ops::AddAssign + core::ops::SubAssign + PartialOrd + Clone> Number<T> {
+    /// `value` limited to the range.
+    fn clamped(&self, value: T) -> T {
+        if value > self.max {
+            self.max.clone()
+        } else if value < self.min {
+            self.min.clone()
+        } else {
+            value
+        }
+    }
+
+    /// How many increments the step in `direction` moves, see [StepScale].
+    fn multiplier(&mut self, input: &State, direction: Option<bool>, ctx: &MenuContext) -> u32 {
+        use wut::gamepad::Button as B;
+
+        match self.scale {
+            StepScale::Off => 1,
+            StepScale::Modifier if input.hold.contains(B::ZR) => 100,
+            StepScale::Modifier if input.hold.contains(B::ZL) => 10,
+            StepScale::Modifier => 1,
+            StepScale::Accelerate => {
+                let (last, steps, idle) = &mut self.streak;
+                let Some(direction) = direction else {
+                    *idle = idle.saturating_add(ctx.elapsed());
+                    return 1;
+                };
+
+                if *last != Some(direction) || *idle > ctx.timings().accelerate_pause {
+                    *steps = 0;
+                }
+                *last = Some(direction);
+                *idle = 0;
+                *steps += 1;
+
+                match *steps {
+                    ..=10 => 1,
+                    11..=20 => 10,
+                    _ => 100,
+                }
+            }
+        }
+    }
+
+    /// Digits shown while editing digit by digit: enough for the bound furthest from zero.
+    fn width(&self) -> u32 {
+        let magnitude = |v: &T| match (self.codec.to)(v) {
+            Value::Int(v) => v.unsigned_abs(),
+            _ => 0,
+        };
+        magnitude(&self.min)
+            .max(magnitude(&self.max))
+            .checked_ilog10()
+            .map_or(1, |digits| digits + 1)
+    }
+
+    /// Digit editing while on top of the navigation stack. A commits the value, clamped to the
+    /// range, B discards it.
+    fn edit(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        let t = input.trigger;
+
+        let Some((value, digit)) = self.digits else {
+            return false;
+        };
+        let step = 10i64.saturating_pow(digit);
+
+        if t.contains(B::A) {
+            let bound = |v: &T, or: i64| match (self.codec.to)(v) {
+                Value::Int(v) => v,
+                _ => or,
+            };
+            let value = value.clamp(bound(&self.min, i64::MIN), bound(&self.max, i64::MAX));
+
+            self.digits = None;
+            self.set_value(&Value::Int(value));
+            stack.pop();
+        } else if t.contains(B::B) {
+            self.digits = None;
+            stack.pop();
+        } else if t.contains(B::Up) {
+            self.digits = Some((value.saturating_add(step), digit));
+        } else if t.contains(B::Down) {
+            self.digits = Some((value.saturating_sub(step), digit));
+        } else if t.contains(B::Left) {
+            self.digits = Some((value, (digit + 1).min(self.width() - 1)));
+        } else if t.contains(B::Right) {
+            self.digits = Some((value, digit.saturating_sub(1)));
+        } else {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl<T: 'static + Display + core::ops::AddAssign + core::ops::SubAssign + PartialOrd + Clone>
+    MenuItem for Number<T>
+{
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        if let Some((value, digit)) = self.digits {
+            let digits = format!(
+                "{:0width$}",
+                value.unsigned_abs(),
+                width = self.width() as usize
+            );
+            let at = digits.len() - 1 - digit as usize;
+            let _ = write!(
+                out,
+                "{}: {}{}[{}]{}",
+                self.text,
+                if value < 0 { "-" } else { "" },
+                &digits[..at],
+                &digits[at..at + 1],
+                &digits[at + 1..]
+            );
+            return;
+        }
+
+        let theme = theme::current();
+        let value = self.value.borrow();
+        let icon = if *value == self.min {
+            theme.increase
+        } else if *value == self.max {
+            theme.decrease
+        } else {
+            theme.adjust
+        };
+
+        let _ = write!(out, "{}: {} {}", self.text, value, icon);
+    }
+
+    #[cfg(feature = "byte-render")]
+    fn render_bytes(&self, buf: &mut [u8]) -> usize {
+        let mut out = ByteWriter::new(buf);
+        if let Some((value, digit)) = self.digits {
+            let mut raw = [0u8; 20];
+            let len = ByteWriter::new(&mut raw)
+                .push_u64(value.unsigned_abs())
+                .len();
+            let mut padded = [b'0'; 20];
+            let width = (self.width() as usize).clamp(len, padded.len());
+            padded[width - len..width].copy_from_slice(&raw[..len]);
+            // SAFETY: ASCII digits only.
+            let digits = unsafe { core::str::from_utf8_unchecked(&padded[..width]) };
+            let at = width - 1 - digit as usize;
+            return out
+                .push_str(&self.text)
+                .push_str(": ")
+                .push_str(if value < 0 { "-" } else { "" })
+                .push_str(&digits[..at])
+                .push_str("[")
+                .push_str(&digits[at..at + 1])
+                .push_str("]")
+                .push_str(&digits[at + 1..])
+                .len();
+        }
+
+        self.render_into(&mut out);
+        out.len()
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        self.control_ctx(input, stack, &mut MenuContext::detached())
+    }
+
+    fn control_ctx(&mut self, input: State, stack: &mut Vec<Node>, ctx: &mut MenuContext) -> bool {
+        use wut::gamepad::Button as B;
+
+        if self.digits.is_some() {
+            return self.edit(input, stack);
+        }
+
+        if let Some(history) = self
+            .history
+            .as_ref()
+            .filter(|_| input.trigger.contains(B::Y))
+        {
+            stack.push(HistoryView::new(&self.text, history.clone()));
+            return true;
+        }
+
+        let before = self.value.borrow().clone();
+        let mut value = before.clone();
+        let mut changed = false;
+        let step = match &mut self.repeat {
+            Some(repeat) => {
+                let timings = self.repeat_timings.unwrap_or(ctx.timings());
+                repeat.step(&input, B::Down, B::Up, &timings, ctx.elapsed())
+            }
+            None if input.trigger.contains(B::Up) => Some(true),
+            None if input.trigger.contains(B::Down) => Some(false),
+            None => None,
+        };
+
+        let times = self.multiplier(&input, step, ctx);
+
+        if step == Some(true) {
+            for _ in 0..times {
+                let mut new = value.clone();
+                new += self.inc.clone();
+
+                value = if new <= self.max {
+                    new
+                } else {
+                    self.max.clone()
+                };
+                if value == self.max {
+                    break;
+                }
+            }
+            changed = true;
+        }
+
+        if step == Some(false) {
+            for _ in 0..times {
+                let mut new = value.clone();
+                new -= self.inc.clone();
+
+                value = if new >= self.min && new < value {
+                    new
+                } else {
+                    self.min.clone()
+                };
+                if value == self.min {
+                    break;
+                }
+            }
+            changed = true;
+        }
+
+        let moved = value != before;
+        *self.value.borrow_mut() = value;
+
+        if input.trigger.contains(B::A) {
+            self.apply();
+        } else if let Some(debounce) = &mut self.debounce
+            && moved
+        {
+            debounce.restart();
+        }
+
+        changed
+    }
+
+    fn tick(&mut self) -> bool {
+        self.tick_ctx(&mut MenuContext::detached())
+    }
+
+    fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+        if self
+            .debounce
+            .as_mut()
+            .is_some_and(|d| d.tick(ctx.elapsed()))
+        {
+            self.apply();
+        }
+
+        let revert = self.history.as_ref().and_then(|h| h.revert.take());
+        let reverted = revert.is_some_and(|value| self.set_value(&value));
+
+        let settled =
+            self.digits.is_none() && self.ramp.as_ref().is_none_or(|r| r.frame >= r.frames);
+        let followed = match self
+            .source
+            .as_ref()
+            .filter(|_| settled)
+            .map(|source| self.clamped(source()))
+        {
+            Some(value) if value != self.applied => {
+                *self.value.borrow_mut() = value.clone();
+                self.applied = value;
+                true
+            }
+            _ => false,
+        };
+
+        if let Some(ramp) = self.ramp.as_mut().filter(|r| r.frame < r.frames) {
+            ramp.frame += 1;
+            self.applied = if ramp.frame == ramp.frames {
+                ramp.to.clone()
+            } else {
+                (ramp.lerp)(&ramp.from, &ramp.to, ramp.frame as f32 / ramp.frames as f32)
+            };
+            (self.f)(&self.applied);
+        }
+        reverted || followed
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some((self.codec.to)(&self.value.borrow()))
+    }
+
+    fn default_value(&self) -> Option<Value> {
+        Some((self.codec.to)(&self.default))
+    }
+
+    fn take_error(&mut self) -> Option<String> {
+        self.error.take()
+    }
+
+    /// Releasing X starts editing integers digit by digit, unless X was held long enough to reset
+    /// the item, see [Overlay::set_reset_hold].
+    fn enters(&self, input: &State) -> bool {
+        input.release.contains(wut::gamepad::Button::X)
+            && matches!((self.codec.to)(&self.value.borrow()), Value::Int(_))
+    }
+
+    fn focus(&mut self) {
+        let value = (self.codec.to)(&self.value.borrow());
+        if let Value::Int(value) = value {
+            self.digits = Some((value, 0));
+        }
+    }
+
+    fn blur(&mut self) {
+        self.digits = None;
+    }
+
+    fn set_value(&mut self, value: &Value) -> bool {
+        let Some(value) = (self.codec.from)(value) else {
+            return false;
+        };
+
+        *self.value.borrow_mut() = self.clamped(value);
+        self.apply();
+        true
+    }
+
+    fn accepts(&self, value: &Value) -> bool {
+        (self.codec.from)(value).is_some()
+    }
+}
+
+// endregion
+
+// region: History
+
+/// Last applied values of a [Number], see [TypedNode::with_history].
+struct History {
+    values: RefCell<VecDeque<Value>>,
+    capacity: usize,
+    /// Value picked in the [HistoryView], applied by the number on its next tick.
+    revert: Cell<Option<Value>>,
+}
+
+impl History {
+    fn new(capacity: usize) -> Self {
+        Self {
+            values: RefCell::new(VecDeque::with_capacity(capacity)),
+            capacity: capacity.max(1),
+            revert: Cell::new(None),
+        }
+    }
+
+    /// Appends `value` unless it is the latest one already.
+    fn record(&self, value: Value) {
+        let mut values = self.values.borrow_mut();
+        if values.back() == Some(&value) {
+            return;
+        }
+        if values.len() == self.capacity {
+            values.pop_front();
+        }
+        values.push_back(value);
+    }
+}
+
+/// Sparkline of a [History] pushed by Y on a [Number].
+struct HistoryView {
+    text: Arc<str>,
+    history: Rc<History>,
+    pos: usize,
+}
+
+impl HistoryView {
+    fn new(text: &Arc<str>, history: Rc<History>) -> Node {
+        let pos = history.values.borrow().len().saturating_sub(2);
+        Rc::new(RefCell::new(Box::new(Self {
+            text: text.clone(),
+            history,
+            pos,
+        })))
+    }
+}
+
+impl MenuItem for HistoryView {
+    fn render(&self) -> String {
+        let theme = theme::current();
+        let values = self.history.values.borrow();
+        let float = |value: &Value| match value {
+            Value::Int(v) => *v as f64,
+            Value::Float(v) => *v,
+            _ => 0.0,
+        };
+        let min = values.iter().map(float).fold(f64::INFINITY, f64::min);
+        let max = values.iter().map(float).fold(f64::NEG_INFINITY, f64::max);
+        let levels = theme.sparkline.len();
+
+        let mut out = format!("{}: ", self.text);
+        for value in values.iter() {
+            let level = if max > min {
+                ((float(value) - min) / (max - min) * (levels - 1) as f64) as usize
+            } else {
+                0
+            };
+            out.push_str(theme.sparkline[level.min(levels - 1)]);
+        }
+        if let Some(value) = values.get(self.pos) {
+            let _ = write!(out, " {}{}{}", theme.prev, value, theme.next);
+        }
+        out
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        let len = self.history.values.borrow().len();
+
+        if input.trigger.contains(B::A) {
+            let value = self.history.values.borrow().get(self.pos).cloned();
+            self.history.revert.set(value);
+            stack.pop();
+        } else if input.trigger.contains(B::B) {
+            stack.pop();
+        } else if input.trigger.contains(B::Left) {
+            self.pos = self.pos.saturating_sub(1);
+        } else if input.trigger.contains(B::Right) {
+            self.pos = (self.pos + 1).min(len.saturating_sub(1));
+        } else {
+            return false;
+        }
+
+        true
+    }
+}
+
+// endregion
+
+// region: Slider
+
+/// Stick deflection below which a [Slider] ignores the stick.
+const SLIDER_DEAD_ZONE: f32 = 0.15;
+
+/// Value shown as a bar, e.g. `Volume: [=====-----] 50%`, for large ranges.
+///
+/// Up/Down moves by `step`, the left stick's X axis adjusts finely: at full deflection the whole
+/// range is crossed in `sweep_ms` milliseconds. Every change is passed to the callback right away.
+/// The stick also moves the cursor while [Overlay::set_analog] is enabled.
+pub struct Slider<T> {
+    text: Arc<str>,
+    enabled: bool,
+    value: T,
+    step: T,
+    min: T,
+    max: T,
+    /// Position including the fraction the stick moved below one unit.
+    exact: f64,
+    /// Units per frame at full deflection.
+    speed: f64,
+    f: Box<dyn Fn(&T)>,
+}
+
+impl<T> Slider<T>
+where
+    T: 'static
+        + Display
+        + core::ops::AddAssign
+        + core::ops::SubAssign
+        + PartialOrd
+        + Copy
+        + NumericValue,
+{
+    pub fn new<F>(text: &str, value: T, step: T, min: T, max: T, sweep_ms: u32, f: F) -> Node
+    where
+        F: 'static + Fn(&T),
+    {
+        let range = Self::float(max) - Self::float(min);
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            value,
+            step,
+            min,
+            max,
+            exact: Self::float(value),
+            speed: range * 1000.0 / (sweep_ms.max(1) as f64 * FRAMES_PER_SECOND as f64),
+            f: Box::new(f),
+        })))
+    }
+
+    /// Like [Slider::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, value: T, step: T, min: T, max: T, sweep_ms: u32, f: F) -> Node
+    where
+        F: 'static + Fn(&T, &mut MenuContext),
+    {
+        Contextual::new(|slot| {
+            Self::new(text, value, step, min, max, sweep_ms, move |value| {
+                slot.with(|ctx| f(value, ctx))
+            })
+        })
+    }
+
+    fn float(value: T) -> f64 {
+        match value.to_value() {
+            Value::Int(v) => v as f64,
+            Value::Float(v) => v,
+            _ => 0.0,
+        }
+    }
+
+    /// Fraction of the range below the value.
+    fn fraction(&self) -> f64 {
+        let range = Self::float(self.max) - Self::float(self.min);
+        if range <= 0.0 {
+            return 0.0;
+        }
+        (Self::float(self.value) - Self::float(self.min)) / range
+    }
+
+    /// Sets the value, clamped to the range, and invokes the callback if it changed.
+    fn update(&mut self, value: T) -> bool {
+        let value = if value > self.max {
+            self.max
+        } else if value < self.min {
+            self.min
+        } else {
+            value
+        };
+
+        if value == self.value {
+            return false;
+        }
+
+        self.value = value;
+        (self.f)(&self.value);
+        true
+    }
+}
+
+impl<T> MenuItem for Slider<T>
+where
+    T: 'static
+        + Display
+        + core::ops::AddAssign
+        + core::ops::SubAssign
+        + PartialOrd
+        + Copy
+        + NumericValue,
+{
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        const SEGMENTS: u32 = 10;
+        let fraction = self.fraction();
+
+        let _ = write!(out, "{}: ", self.text);
+        bar(out, (fraction * SEGMENTS as f64) as u32, SEGMENTS);
+        let _ = write!(out, " {}%", (fraction * 100.0) as u32);
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        self.control_ctx(input, stack, &mut MenuContext::detached())
+    }
+
+    fn control_ctx(&mut self, input: State, _stack: &mut Vec<Node>, ctx: &mut MenuContext) -> bool {
+        use wut::gamepad::Button as B;
+
+        let mut value = self.value;
+        if input.trigger.contains(B::Up) {
+            value = if Self::float(self.max) - Self::float(value) > Self::float(self.step) {
+                value += self.step;
+                value
+            } else {
+                self.max
+            };
+        }
+        if input.trigger.contains(B::Down) {
+            value = if Self::float(value) - Self::float(self.min) > Self::float(self.step) {
+                value -= self.step;
+                value
+            } else {
+                self.min
+            };
+        }
+
+        if value != self.value {
+            self.exact = Self::float(value);
+            return self.update(value);
+        }
+
+        let (x, _) = left_stick(&input);
+        if x.abs() < SLIDER_DEAD_ZONE {
+            return false;
+        }
+
+        let (min, max) = (Self::float(self.min), Self::float(self.max));
+        self.exact = (self.exact + x as f64 * self.speed * ctx.elapsed() as f64).clamp(min, max);
+        match T::from_value(&Value::Float(self.exact)) {
+            Some(value) => self.update(value),
+            None => false,
+        }
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some(self.value.to_value())
+    }
+
+    fn set_value(&mut self, value: &Value) -> bool {
+        let Some(value) = T::from_value(value) else {
+            return false;
+        };
+
+        self.update(value);
+        self.exact = Self::float(self.value);
+        true
+    }
+
+    fn accepts(&self, value: &Value) -> bool {
+        T::from_value(value).is_some()
+    }
+}
+
+// endregion
+
+// region: Select
+
+pub struct Selection<T> {
+    pub name: String,
+    pub value: T,
+}
+
+impl<T> From<(&str, T)> for Selection<T> {
+    fn from(value: (&str, T)) -> Self {
+        Self {
+            name: String::from(value.0),
+            value: value.1,
+        }
+    }
+}
+
+impl<T> From<(String, T)> for Selection<T> {
+    fn from((name, value): (String, T)) -> Self {
+        Self { name, value }
+    }
+}
+
+impl From<&str> for Selection<String> {
+    fn from(value: &str) -> Self {
+        Self {
+            name: String::from(value),
+            value: String::from(value),
+        }
+    }
+}
+
+impl<T: Display> From<T> for Selection<T> {
+    fn from(value: T) -> Self {
+        Self {
+            name: format!("{}", value),
+            value,
+        }
+    }
+}
+
+pub struct Select<T> {
+    text: Arc<str>,
+    enabled: bool,
+    options: Vec<Selection<T>>,
+    index: Rc<Cell<usize>>,
+    f: Box<dyn Fn(usize, &Selection<T>)>,
+    debounce: Option<Debounce>,
+    default: usize,
+    /// Whether Up on the last option selects the first and Down on the first the last.
+    wrap: bool,
+}
+
+impl<T: 'static> Select<T> {
+    pub fn new<F>(text: &str, options: Vec<impl Into<Selection<T>>>, f: F) -> Node
+    where
+        F: 'static + Fn(usize, &Selection<T>),
+    {
+        Self::typed(text, options, f).into()
+    }
+
+    /// Like [Select::new] with a callback that mutates its captures. The callback must not select
+    /// again.
+    pub fn new_mut<F>(text: &str, options: Vec<impl Into<Selection<T>>>, f: F) -> Node
+    where
+        F: 'static + FnMut(usize, &Selection<T>),
+    {
+        let f = RefCell::new(f);
+        Self::new(text, options, move |index, selection| {
+            (f.borrow_mut())(index, selection)
+        })
+    }
+
+    /// Like [Select::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, options: Vec<impl Into<Selection<T>>>, f: F) -> Node
+    where
+        F: 'static + Fn(usize, &Selection<T>, &mut MenuContext),
+    {
+        Contextual::new(|slot| {
+            Self::new(text, options, move |index, selection| {
+                slot.with(|ctx| f(index, selection, ctx))
+            })
+        })
+    }
+
+    pub fn typed<F>(text: &str, options: Vec<impl Into<Selection<T>>>, f: F) -> TypedNode<Self>
+    where
+        F: 'static + Fn(usize, &Selection<T>),
+    {
+        Self::with_index(text, options, 0, f)
+    }
+
+    /// Like [Select::typed], but starting at the option at `index` (clamped), which is also the
+    /// default. The callback is not invoked for it.
+    pub fn with_index<F>(
+        text: &str,
+        options: Vec<impl Into<Selection<T>>>,
+        index: usize,
+        f: F,
+    ) -> TypedNode<Self>
+    where
+        F: 'static + Fn(usize, &Selection<T>),
+    {
+        let options: Vec<Selection<T>> = options.into_iter().map(Into::into).collect();
+        let index = index.min(options.len().saturating_sub(1));
+        TypedNode::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            options,
+            index: Rc::new(Cell::new(index)),
+            f: Box::new(f),
+            debounce: None,
+            default: index,
+            wrap: false,
+        })
+    }
+
+    /// Like [Select::with_index], starting at the first option holding `value`, or the first
+    /// option if none does.
+    pub fn with_value<F>(
+        text: &str,
+        options: Vec<impl Into<Selection<T>>>,
+        value: &T,
+        f: F,
+    ) -> TypedNode<Self>
+    where
+        T: PartialEq,
+        F: 'static + Fn(usize, &Selection<T>),
+    {
+        let options: Vec<Selection<T>> = options.into_iter().map(Into::into).collect();
+        let index = options.iter().position(|o| o.value == *value).unwrap_or(0);
+        Self::with_index(text, options, index, f)
+    }
+
+    /// Like [Select::with_value] with the [Options::variants] of `T` as options, so the
+    /// callback gets the selected value only.
+    pub fn from_options<F>(text: &str, value: &T, f: F) -> TypedNode<Self>
+    where
+        T: Options,
+        F: 'static + Fn(&T),
+    {
+        Self::with_value(text, T::variants(), value, move |_, selection| {
+            f(&selection.value)
+        })
+    }
+
+    /// Like [Select::new], but Up/Down also pass the option to the callback once it has been
+    /// left alone for `debounce_ms` milliseconds.
+    pub fn debounced<F>(
+        text: &str,
+        options: Vec<impl Into<Selection<T>>>,
+        debounce_ms: u32,
+        f: F,
+    ) -> Node
+    where
+        F: 'static + Fn(usize, &Selection<T>),
+    {
+        let select = Self::typed(text, options, f);
+        select.borrow_mut().debounce = Some(Debounce::new(debounce_ms));
+        select.node()
+    }
+}
+
+impl<T> Select<T> {
+    /// Option a [Value::Index] or the name of an option as [Value::Text] refers to.
+    fn index_of(&self, value: &Value) -> Option<usize> {
+        match value {
+            Value::Index(index) if *index < self.options.len() => Some(*index),
+            Value::Text(name) => self.options.iter().position(|o| &o.name == name),
+            _ => None,
+        }
+    }
+}
+
+impl<T: 'static> TypedNode<Select<T>> {
+    pub fn index(&self) -> usize {
+        self.state.get()
+    }
+
+    /// Selects the option at `index` and passes it to the callback.
+    pub fn set_index(&self, index: usize) -> bool {
+        self.set_value(&Value::Index(index))
+    }
+
+    /// Makes Up on the last option select the first and Down on the first the last.
+    pub fn wrapping(self) -> Self {
+        self.borrow_mut().wrap = true;
+        self
+    }
+}
+
+impl<T: 'static> Widget for Select<T> {
+    type State = Rc<Cell<usize>>;
+
+    fn state(&self) -> Self::State {
+        self.index.clone()
+    }
+}
+
+impl<T: 'static> MenuItem for Select<T> {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let theme = theme::current();
+        let index = self.index.get();
+        let icon = if self.wrap {
+            theme.adjust
+        } else if index == 0 {
+            theme.increase
+        } else if index == self.options.len() - 1 {
+            theme.decrease
+        } else {
+            theme.adjust
+        };
+
+        let _ = write!(out, "{}: {} {}", self.text, self.options[index].name, icon);
+    }
+
+    #[cfg(feature = "byte-render")]
+    fn render_bytes(&self, buf: &mut [u8]) -> usize {
+        let theme = theme::current();
+        let index = self.index.get();
+        let icon = if self.wrap {
+            theme.adjust
+        } else if index == 0 {
+            theme.increase
+        } else if index == self.options.len() - 1 {
+            theme.decrease
+        } else {
+            theme.adjust
+        };
+
+        ByteWriter::new(buf)
+            .push_str(&self.text)
+            .push_str(": ")
+            .push_str(&self.options[index].name)
+            .push_str(" ")
+            .push_str(icon)
+            .len()
+    }
+
+    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        let before = self.index.get();
+        let mut changed = false;
+        if input.trigger.contains(B::Up) {
+            if self.index.get() < self.options.len() - 1 {
+                self.index.set(self.index.get() + 1)
+            } else if self.wrap {
+                self.index.set(0);
+            }
+            changed = true;
+        }
+
+        if input.trigger.contains(B::Down) {
+            if self.index.get() > 0 {
+                self.index.set(self.index.get() - 1);
+            } else if self.wrap {
+                self.index.set(self.options.len() - 1);
+            }
+            changed = true;
+        }
+
+        if input.trigger.contains(B::A) {
+            if let Some(debounce) = &mut self.debounce {
+                debounce.cancel();
+            }
+            let index = self.index.get();
+            (self.f)(index, &self.options[index]);
+        } else if let Some(debounce) = &mut self.debounce
+            && self.index.get() != before
+        {
+            debounce.restart();
+        }
+
+        changed
+    }
+
+    fn tick(&mut self) -> bool {
+        self.tick_ctx(&mut MenuContext::detached())
+    }
+
+    fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+        if self
+            .debounce
+            .as_mut()
+            .is_some_and(|d| d.tick(ctx.elapsed()))
+        {
+            let index = self.index.get();
+            (self.f)(index, &self.options[index]);
+        }
+        false
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Index(self.index.get()))
+    }
+
+    fn default_value(&self) -> Option<Value> {
+        Some(Value::Index(self.default))
+    }
+
+    fn preview(&self) -> Option<String> {
+        let option = self.options.get(self.index.get())?;
+        Some(format!("{}: {}", self.text, option.name))
+    }
+
+    /// Accepts a [Value::Index] or the name of an option as [Value::Text].
+    fn set_value(&mut self, value: &Value) -> bool {
+        let Some(index) = self.index_of(value) else {
+            return false;
+        };
+
+        self.index.set(index);
+        (self.f)(index, &self.options[index]);
+        true
+    }
+
+    fn accepts(&self, value: &Value) -> bool {
+        self.index_of(value).is_some()
+    }
+}
+
+// endregion
+
+// region: Toggle
+
+/// State of a [Toggle], shared with its [TypedNode].
+pub struct ToggleState {
+    value: Cell<bool>,
+    f: Box<dyn Fn(bool)>,
+}
+
+impl ToggleState {
+    fn set(&self, value: bool) {
+        self.value.set(value);
+        (self.f)(value);
+    }
+}
+
+pub struct Toggle {
+    text: Arc<str>,
+    enabled: bool,
+    state: Rc<ToggleState>,
+    default: bool,
+    error: Failure,
+    /// Shared value followed each tick, see [TypedNode::following].
+    source: Option<Box<dyn Fn() -> bool>>,
+}
+
+impl Toggle {
+    pub fn new<F>(text: &str, value: bool, f: F) -> Node
+    where
+        F: 'static + Fn(bool),
+    {
+        Self::typed(text, value, f).into()
+    }
+
+    /// Like [Toggle::new] with a callback that mutates its captures. The callback must not set
+    /// the toggle again.
+    pub fn new_mut<F>(text: &str, value: bool, f: F) -> Node
+    where
+        F: 'static + FnMut(bool),
+    {
+        let f = RefCell::new(f);
+        Self::new(text, value, move |value| (f.borrow_mut())(value))
+    }
+
+    /// Like [Toggle::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, value: bool, f: F) -> Node
+    where
+        F: 'static + Fn(bool, &mut MenuContext),
+    {
+        Contextual::new(|slot| Self::new(text, value, move |value| slot.with(|ctx| f(value, ctx))))
+    }
+
+    pub fn typed<F>(text: &str, value: bool, f: F) -> TypedNode<Self>
+    where
+        F: 'static + Fn(bool),
+    {
+        TypedNode::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            state: Rc::new(ToggleState {
+                value: Cell::new(value),
+                f: Box::new(f),
+            }),
+            default: value,
+            error: Failure::default(),
+            source: None,
+        })
+    }
+
+    /// Toggle kept in sync with `shared` both ways: changes are stored into it and values stored
+    /// by others are shown, so game code can read the setting directly.
+    pub fn bind(text: &str, shared: Arc<AtomicBool>) -> TypedNode<Self> {
+        let value = shared.load(Ordering::Relaxed);
+        Self::typed(text, value, {
+            let shared = shared.clone();
+            move |value| shared.store(value, Ordering::Relaxed)
+        })
+        .following(move || shared.load(Ordering::Relaxed))
+    }
+
+    /// Like [Toggle::new] with a fallible callback.
+    pub fn try_new<F>(text: &str, value: bool, f: F) -> Node
+    where
+        F: 'static + Fn(bool) -> Result<(), String>,
+    {
+        let error = Failure::default();
+        let toggle = Self::typed(text, value, {
+            let error = error.clone();
+            move |value| error.record(f(value))
+        });
+        toggle.borrow_mut().error = error;
+        toggle.node()
+    }
+}
+
+impl TypedNode<Toggle> {
+    pub fn get(&self) -> bool {
+        self.state.value.get()
+    }
+
+    /// Sets the value and passes it to the callback.
+    pub fn set(&self, value: bool) {
+        self.state.set(value);
+    }
+
+    /// Shows the value returned by `source` whenever it differs, e.g. a setting also changed by
+    /// the game. The callback is not invoked for it.
+    pub fn following<F>(self, source: F) -> Self
+    where
+        F: 'static + Fn() -> bool,
+    {
+        self.borrow_mut().source = Some(Box::new(source));
+        self
+    }
+}
+
+impl Widget for Toggle {
+    type State = Rc<ToggleState>;
+
+    fn state(&self) -> Self::State {
+        self.state.clone()
+    }
+}
+
+impl MenuItem for Toggle {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let theme = theme::current();
+        let _ = write!(
+            out,
+            "{} [{}]",
+            self.text,
+            if self.state.value.get() {
+                theme.checked
+            } else {
+                theme.unchecked
+            }
+        );
+    }
+
+    #[cfg(feature = "byte-render")]
+    fn render_bytes(&self, buf: &mut [u8]) -> usize {
+        let theme = theme::current();
+        ByteWriter::new(buf)
+            .push_str(&self.text)
+            .push_str(" [")
+            .push_str(if self.state.value.get() {
+                theme.checked
+            } else {
+                theme.unchecked
+            })
+            .push_str("]")
+            .len()
+    }
+
+    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        let mut changed = false;
+
+        if input.trigger.contains(B::A) {
+            self.state.set(!self.state.value.get());
+            changed = true;
+        }
+
+        changed
+    }
+
+    fn tick(&mut self) -> bool {
+        match self.source.as_ref().map(|source| source()) {
+            Some(value) if value != self.state.value.get() => {
+                self.state.value.set(value);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Bool(self.state.value.get()))
+    }
+
+    fn default_value(&self) -> Option<Value> {
+        Some(Value::Bool(self.default))
+    }
+
+    fn take_error(&mut self) -> Option<String> {
+        self.error.take()
+    }
+
+    fn set_value(&mut self, value: &Value) -> bool {
+        let Value::Bool(value) = value else {
+            return false;
+        };
+
+        self.state.set(*value);
+        true
+    }
+
+    fn actionable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self) {
+        self.state.set(!self.state.value.get());
+    }
+}
+
+// endregion
+
+// region: Locked
+
+pub enum Code {
+    /// Buttons to press in order. `B` cancels the entry and can't be part of the sequence.
+    Sequence(Vec<wut::gamepad::Button>),
+    /// Decimal digits; Up/Down changes the selected digit, Left/Right moves and A confirms.
+    Pin(String),
+}
+
+/// Wrapper that makes its item usable only after the code was entered.
+///
+/// The unlock is kept for the lifetime of the node or until [Locked::lock] is called.
+pub struct Locked {
+    inner: Node,
+    code: Code,
+    unlocked: bool,
+    entering: bool,
+    progress: usize,
+    digits: Vec<u8>,
+    cursor: usize,
+    failed: bool,
+}
+
+impl Locked {
+    /// An empty code leaves the item unlocked.
+    pub fn new(inner: Node, code: Code) -> Node {
+        let unlocked = match &code {
+            Code::Sequence(sequence) => sequence.is_empty(),
+            Code::Pin(pin) => pin.is_empty(),
+        };
+
+        Rc::new(RefCell::new(Box::new(Self {
+            inner,
+            code,
+            unlocked,
+            entering: false,
+            progress: 0,
+            digits: vec![],
+            cursor: 0,
+            failed: false,
+        })))
+    }
+
+    pub fn is_unlocked(&self) -> bool {
+        self.unlocked
+    }
+
+    pub fn lock(&mut self) {
+        self.unlocked = false;
+    }
+
+    fn reset(&mut self) {
+        self.progress = 0;
+        self.cursor = 0;
+        self.digits = match &self.code {
+            Code::Pin(pin) => vec![0; pin.len()],
+            Code::Sequence(_) => vec![],
+        };
+    }
+
+    fn unlock(&mut self, stack: &mut Vec<Node>) {
+        self.unlocked = true;
+        self.entering = false;
+
+        if self.inner.borrow().focusable() {
+            self.inner.borrow_mut().focus();
+        } else {
+            stack.pop();
+        }
+    }
+
+    fn fail(&mut self) {
+        self.failed = true;
+        self.reset();
+    }
+}
+
+impl MenuItem for Locked {
+    forward_menu_item!(
+        select,
+        tick,
+        tick_ctx,
+        children,
+        label,
+        enabled,
+        set_enabled,
+        help,
+        sounds,
+    );
+
+    fn render_into(&self, out: &mut dyn Write) {
+        if self.unlocked {
+            self.inner.borrow().render_into(out);
+        } else {
+            let _ = out.write_str(&self.render());
+        }
+    }
+
+    fn render(&self) -> String {
+        if self.unlocked {
+            return self.inner.borrow().render();
+        }
+
+        if !self.entering {
+            return self.summary();
+        }
+
+        let status = if self.failed { " wrong" } else { "" };
+
+        match &self.code {
+            Code::Sequence(_) => format!("Code: {}{}", "*".repeat(self.progress), status),
+            Code::Pin(_) => {
+                let mut digits = String::new();
+                for (i, digit) in self.digits.iter().enumerate() {
+                    if i == self.cursor {
+                        digits.push_str(&format!("[{}]", digit));
+                    } else {
+                        digits.push_str(&format!(" {} ", digit));
+                    }
+                }
+                format!("PIN:{}{}", digits, status)
+            }
+        }
+    }
+
+    fn summary(&self) -> String {
+        if self.unlocked {
+            self.inner.borrow().summary()
+        } else {
+            format!(
+                "{} {}",
+                self.inner.borrow().summary(),
+                theme::current().locked
+            )
+        }
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        self.control_ctx(input, stack, &mut MenuContext::detached())
+    }
+
+    fn control_ctx(&mut self, input: State, stack: &mut Vec<Node>, ctx: &mut MenuContext) -> bool {
+        use wut::gamepad::Button as B;
+
+        if self.unlocked {
+            return self.inner.borrow_mut().control_ctx(input, stack, ctx);
+        }
+
+        if !self.entering || input.trigger.is_empty() {
+            return false;
+        }
+
+        if input.trigger.contains(B::B) {
+            self.entering = false;
+            if stack.len() > 1 {
+                stack.pop();
+            }
+            return true;
+        }
+
+        self.failed = false;
+
+        match &self.code {
+            Code::Sequence(sequence) => {
+                if input.trigger.contains(sequence[self.progress]) {
+                    self.progress += 1;
+                    if self.progress == sequence.len() {
+                        self.unlock(stack);
+                    }
+                } else {
+                    self.fail();
+                }
+            }
+            Code::Pin(pin) => {
+                if input.trigger.contains(B::Up) {
+                    self.digits[self.cursor] = (self.digits[self.cursor] + 1) % 10;
+                } else if input.trigger.contains(B::Down) {
+                    self.digits[self.cursor] = (self.digits[self.cursor] + 9) % 10;
+                } else if input.trigger.contains(B::Left) {
+                    self.cursor = self.cursor.saturating_sub(1);
+                } else if input.trigger.contains(B::Right) {
+                    self.cursor = (self.cursor + 1).min(self.digits.len().saturating_sub(1));
+                } else if input.trigger.contains(B::A) {
+                    let matches = pin
+                        .bytes()
+                        .map(|c| c.wrapping_sub(b'0'))
+                        .eq(self.digits.iter().copied());
+                    if matches {
+                        self.unlock(stack);
+                    } else {
+                        self.fail();
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    fn focus(&mut self) {
+        if self.unlocked {
+            self.inner.borrow_mut().focus();
+        } else {
+            self.entering = true;
+            self.failed = false;
+            self.reset();
+        }
+    }
+
+    fn blur(&mut self) {
+        if self.unlocked {
+            self.inner.borrow_mut().blur();
+        }
+        self.entering = false;
+    }
+
+    fn focusable(&self) -> bool {
+        !self.unlocked || self.inner.borrow().focusable()
+    }
+
+    fn interactive(&self) -> bool {
+        !self.unlocked || self.inner.borrow().interactive()
+    }
+
+    fn accessible(&self) -> bool {
+        self.unlocked
+    }
+}
+
+// endregion
+
+// region: Gate
+
+/// [Toggle] in front of a submenu: the submenu can only be entered with A while the gate is on.
+/// X switches the gate and reports the new state to the callback.
+pub struct Gate {
+    text: Arc<str>,
+    enabled: bool,
+    value: bool,
+    inner: Node,
+    entered: bool,
+    f: Box<dyn Fn(bool)>,
+}
+
+impl Gate {
+    pub fn new<F>(text: &str, value: bool, inner: Node, f: F) -> Node
+    where
+        F: 'static + Fn(bool),
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            value,
+            inner,
+            entered: false,
+            f: Box::new(f),
+        })))
+    }
+
+    /// Like [Gate::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, value: bool, inner: Node, f: F) -> Node
+    where
+        F: 'static + Fn(bool, &mut MenuContext),
+    {
+        Contextual::new(|slot| {
+            Self::new(text, value, inner, move |value| {
+                slot.with(|ctx| f(value, ctx))
+            })
+        })
+    }
+
+    pub fn value(&self) -> bool {
+        self.value
+    }
+}
+
+impl MenuItem for Gate {
+    forward_menu_item!(children, tick, tick_ctx);
+
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        if self.entered {
+            self.inner.borrow().render_into(out);
+        } else {
+            let _ = out.write_str(&self.summary());
+        }
+    }
+
+    fn summary(&self) -> String {
+        let theme = theme::current();
+        if self.value {
+            format!("{} [{}] {}", self.text, theme.checked, theme.submenu)
+        } else {
+            format!("{} [{}]", self.text, theme.unchecked)
+        }
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        self.control_ctx(input, stack, &mut MenuContext::detached())
+    }
+
+    fn control_ctx(&mut self, input: State, stack: &mut Vec<Node>, ctx: &mut MenuContext) -> bool {
+        use wut::gamepad::Button as B;
+
+        if self.entered {
+            let depth = stack.len();
+            let changed = self.inner.borrow_mut().control_ctx(input, stack, ctx);
+            if stack.len() < depth {
+                self.entered = false;
+            }
+            return changed;
+        }
+
+        if input.trigger.contains(B::X) {
+            self.value = !self.value;
+            (self.f)(self.value);
+            return true;
+        }
+
+        false
+    }
+
+    fn focus(&mut self) {
+        self.entered = true;
+        self.inner.borrow_mut().focus();
+    }
+
+    fn blur(&mut self) {
+        self.entered = false;
+        self.inner.borrow_mut().blur();
+    }
+
+    fn focusable(&self) -> bool {
+        self.value && self.inner.borrow().focusable()
+    }
+
+    fn accessible(&self) -> bool {
+        self.value
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Bool(self.value))
+    }
+
+    fn set_value(&mut self, value: &Value) -> bool {
+        let Value::Bool(value) = value else {
+            return false;
+        };
+
+        self.value = *value;
+        (self.f)(self.value);
+        true
+    }
+}
+
+// endregion
+
+// region: Badge
+
+/// Decorates another item with a short dynamic badge, e.g. `Cheats (3 active) ⏎`. The badge is
+/// taken from the closure on every render and polled each tick; an empty badge is not shown.
+///
+/// Otherwise the badge behaves like the decorated item, which is also what [find_path] returns
+/// for its label.
+pub struct Badge {
+    inner: Node,
+    f: Box<dyn Fn() -> String>,
+    shown: String,
+    entered: bool,
+}
+
+impl Badge {
+    pub fn new<F>(inner: Node, f: F) -> Node
+    where
+        F: 'static + Fn() -> String,
+    {
+        let shown = f();
+        Rc::new(RefCell::new(Box::new(Self {
+            inner,
+            f: Box::new(f),
+            shown,
+            entered: false,
+        })))
+    }
+}
+
+impl MenuItem for Badge {
+    forward_menu_item!(
+        render_decorated,
+        owns,
+        inline,
+        children,
+        decorates,
+        select,
+        accessible,
+        actionable,
+        activate,
+        activate_ctx,
+        label,
+        enabled,
+        set_enabled,
+        help,
+        sounds,
+        default_value,
+        take_error,
+        preview,
+        current,
+        select_entry,
+        value,
+        set_value,
+        accepts,
+        focusable,
+        interactive,
+        enters,
+    );
+
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let inner = self.inner.borrow();
+        let badge = (self.f)();
+        if self.entered || badge.is_empty() {
+            inner.render_into(out);
+        } else {
+            inner.render_decorated(&badge, out);
+        }
+    }
+
+    #[cfg(feature = "byte-render")]
+    fn render_bytes(&self, buf: &mut [u8]) -> usize {
+        let inner = self.inner.borrow();
+        let badge = (self.f)();
+        if self.entered || badge.is_empty() {
+            inner.render_bytes(buf)
+        } else {
+            let mut out = ByteWriter::new(buf);
+            inner.render_decorated(&badge, &mut out);
+            out.len()
+        }
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        self.control_ctx(input, stack, &mut MenuContext::detached())
+    }
+
+    fn control_ctx(&mut self, input: State, stack: &mut Vec<Node>, ctx: &mut MenuContext) -> bool {
+        let depth = stack.len();
+        let changed = self.inner.borrow_mut().control_ctx(input, stack, ctx);
+        if self.entered && stack.len() < depth {
+            self.entered = false;
+        }
+        changed
+    }
+
+    fn tick(&mut self) -> bool {
+        self.tick_ctx(&mut MenuContext::detached())
+    }
+
+    fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+        let changed = self.inner.borrow_mut().tick_ctx(ctx);
+        let badge = (self.f)();
+        if badge == self.shown {
+            return changed;
+        }
+
+        self.shown = badge;
+        true
+    }
+
+    fn focus(&mut self) {
+        self.entered = true;
+        self.inner.borrow_mut().focus();
+    }
+
+    fn blur(&mut self) {
+        self.entered = false;
+        self.inner.borrow_mut().blur();
+    }
+
+    fn summary(&self) -> String {
+        let mut out = String::new();
+        let badge = (self.f)();
+        if badge.is_empty() {
+            return self.inner.borrow().summary();
+        }
+        self.inner.borrow().render_decorated(&badge, &mut out);
+        out
+    }
+}
+
+// endregion
+
+// region: Id
+
+/// Gives another item an [MenuItem::id] to find it by with [find] or [Overlay::find]. Otherwise
+/// behaves like the decorated item.
+pub struct Id {
+    id: String,
+    inner: Node,
+}
+
+impl Id {
+    pub fn new(id: &str, inner: Node) -> Node {
+        Rc::new(RefCell::new(Box::new(Self {
+            id: String::from(id),
+            inner,
+        })))
+    }
+}
+
+impl MenuItem for Id {
+    forward_menu_item!(
+        render,
+        render_into,
+        render_bytes,
+        render_decorated,
+        control,
+        control_ctx,
+        tick,
+        tick_ctx,
+        focus,
+        blur,
+        owns,
+        inline,
+        children,
+        decorates,
+        summary,
+        select,
+        accessible,
+        actionable,
+        activate,
+        activate_ctx,
+        label,
+        enabled,
+        set_enabled,
+        help,
+        sounds,
+        default_value,
+        take_error,
+        preview,
+        current,
+        select_entry,
+        value,
+        set_value,
+        accepts,
+        focusable,
+        interactive,
+        enters,
+    );
+
+    fn id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+}
+
+// endregion
+
+// region: Help
+
+/// Gives another item [MenuItem::help] text. Otherwise behaves like the decorated item.
+pub struct Help {
+    help: String,
+    inner: Node,
+}
+
+impl Help {
+    pub fn new(help: &str, inner: Node) -> Node {
+        Rc::new(RefCell::new(Box::new(Self {
+            help: String::from(help),
+            inner,
+        })))
+    }
+}
+
+impl<W: Widget> TypedNode<W> {
+    /// Wraps the widget in [Help]. Keep a clone of the typed node for access to the widget.
+    pub fn with_help(self, help: &str) -> Node {
+        Help::new(help, self.node)
+    }
+}
+
+impl MenuItem for Help {
+    forward_menu_item!(
+        render,
+        render_into,
+        render_bytes,
+        render_decorated,
+        control,
+        control_ctx,
+        tick,
+        tick_ctx,
+        focus,
+        blur,
+        owns,
+        inline,
+        children,
+        decorates,
+        summary,
+        select,
+        accessible,
+        actionable,
+        activate,
+        activate_ctx,
+        label,
+        enabled,
+        set_enabled,
+        sounds,
+        default_value,
+        take_error,
+        preview,
+        current,
+        select_entry,
+        value,
+        set_value,
+        accepts,
+        focusable,
+        interactive,
+        enters,
+    );
+
+    fn help(&self) -> Option<String> {
+        Some(self.help.clone())
+    }
+}
+
+// endregion
+
+// region: Cues
+
+/// Gives another item its own [MenuItem::sounds], e.g. a distinct tone for a dangerous action
+/// or [sound::Sounds::SILENT] for one used often. Otherwise behaves like the decorated item.
+pub struct Cues {
+    sounds: sound::Sounds,
+    inner: Node,
+}
+
+impl Cues {
+    pub fn new(sounds: sound::Sounds, inner: Node) -> Node {
+        Rc::new(RefCell::new(Box::new(Self { sounds, inner })))
+    }
+}
+
+impl<W: Widget> TypedNode<W> {
+    /// Wraps the widget in [Cues]. Keep a clone of the typed node for access to the widget.
+    pub fn with_sounds(self, sounds: sound::Sounds) -> Node {
+        Cues::new(sounds, self.node)
+    }
+}
+
+impl MenuItem for Cues {
+    forward_menu_item!(
+        render,
+        render_into,
+        render_bytes,
+        render_decorated,
+        control,
+        control_ctx,
+        tick,
+        tick_ctx,
+        focus,
+        blur,
+        owns,
+        inline,
+        children,
+        decorates,
+        summary,
+        select,
+        accessible,
+        actionable,
+        activate,
+        activate_ctx,
+        label,
+        enabled,
+        set_enabled,
+        help,
+        default_value,
+        take_error,
+        preview,
+        current,
+        select_entry,
+        value,
+        set_value,
+        accepts,
+        focusable,
+        interactive,
+        enters,
+    );
+
+    fn sounds(&self) -> Option<sound::Sounds> {
+        Some(self.sounds)
+    }
+}
+
+// endregion
+
+// region: Preset
+
+/// Selects one of several named bundles of `(path, value)` assignments with Up/Down and applies
+/// it with A. Paths are resolved with [find_path] from the root. Only if every item is found and
+/// [accepts](MenuItem::accepts) its value, the values are set through [MenuItem::set_value] in
+/// the listed order; otherwise nothing is set. If an item rejects its value anyway, the items set
+/// before it are set back to their previous values, invoking their callbacks again.
+pub struct Preset {
+    text: Arc<str>,
+    enabled: bool,
+    presets: Vec<(String, Vec<(String, Value)>)>,
+    index: usize,
+    applying: bool,
+    failed: usize,
+}
+
+impl Preset {
+    pub fn new(text: &str, presets: Vec<(&str, Vec<(&str, Value)>)>) -> Node {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            presets: presets
+                .into_iter()
+                .map(|(name, values)| {
+                    (
+                        String::from(name),
+                        values
+                            .into_iter()
+                            .map(|(path, value)| (String::from(path), value))
+                            .collect(),
+                    )
+                })
+                .collect(),
+            index: 0,
+            applying: false,
+            failed: 0,
+        })))
+    }
+
+    /// Number of assignments that kept the preset from being applied the last time.
+    pub fn failed(&self) -> usize {
+        self.failed
+    }
+
+    fn apply(&mut self, root: &Node) {
+        let values = &self.presets[self.index].1;
+        let targets: Vec<_> = values
+            .iter()
+            .map(|(path, value)| {
+                find_path(root, path).filter(|node| {
+                    !watchdog::is_faulted(node)
+                        && node.try_borrow().is_ok_and(|item| item.accepts(value))
+                })
+            })
+            .collect();
+
+        self.failed = targets.iter().filter(|node| node.is_none()).count();
+        if self.failed > 0 {
+            return;
+        }
+
+        let mut set: Vec<(&Node, Option<Value>)> = vec![];
+        for (node, (_, value)) in targets.iter().flatten().zip(values) {
+            let previous = node.try_borrow().ok().and_then(|item| item.value());
+            if !watchdog::set_value(node, value) {
+                self.failed = 1;
+                break;
+            }
+            set.push((node, previous));
+        }
+
+        if self.failed > 0 {
+            for (node, previous) in set.into_iter().rev() {
+                if let Some(previous) = previous {
+                    watchdog::set_value(node, &previous);
+                }
+            }
+        }
+    }
+}
+
+impl MenuItem for Preset {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let theme = theme::current();
+        let icon = if self.index == 0 {
+            theme.increase
+        } else if self.index == self.presets.len() - 1 {
+            theme.decrease
+        } else {
+            theme.adjust
+        };
+
+        let name = self
+            .presets
+            .get(self.index)
+            .map(|(name, _)| name.as_str())
+            .unwrap_or_default();
+
+        let _ = write!(out, "{}: {} {}", self.text, name, icon);
+        if self.failed > 0 {
+            let _ = write!(out, " ({} failed)", self.failed);
+        }
+    }
+
+    // The preset is applied as the navigation head (pushed by the parent on A), so the parent
+    // is not borrowed while paths are resolved.
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+
+        if self.applying {
+            let root = stack[0].clone();
+            self.apply(&root);
+            self.applying = false;
+            stack.pop();
+            return true;
+        }
+
+        let mut changed = false;
+        if input.trigger.contains(B::Up) && self.index + 1 < self.presets.len() {
+            self.index += 1;
+            self.failed = 0;
+            changed = true;
+        }
+
+        if input.trigger.contains(B::Down) && self.index > 0 {
+            self.index -= 1;
+            self.failed = 0;
+            changed = true;
+        }
+
+        changed
+    }
+
+    fn focus(&mut self) {
+        self.applying = true;
+    }
+
+    fn blur(&mut self) {
+        self.applying = false;
+    }
+
+    fn focusable(&self) -> bool {
+        !self.presets.is_empty()
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+}
+
+// endregion
+
+// region: Palette
+
+/// Characters the [Palette] query is typed from.
+const PALETTE_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789 ";
+
+/// Sorting key for `text` containing the characters of `query` in order, ignoring ASCII case.
+/// Lower is a better match.
+fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let mut text = text.chars().enumerate();
+    let mut score = 0;
+    let mut next = 0;
+
+    for q in query.chars() {
+        let (i, _) = text.find(|(_, c)| c.eq_ignore_ascii_case(&q))?;
+        score += i - next;
+        next = i + 1;
+    }
+
+    Some(score)
+}
+
+/// Searchable list of every [MenuItem::actionable] item reachable in the tree.
+///
+/// Up/Down picks a character, X appends it to the query and Y deletes the last one. Left/Right
+/// moves through the matches, A activates the selected one and B closes the palette.
+///
+/// Opened as the navigation head by [Overlay::set_palette_combo] or from a parent menu.
+pub struct Palette {
+    entries: Vec<(String, Node)>,
+    stale: bool,
+    query: String,
+    pick: usize,
+    matches: Vec<usize>,
+    pos: usize,
+}
+
+impl Palette {
+    pub fn new() -> Node {
+        Rc::new(RefCell::new(Box::new(Self::empty())))
+    }
+
+    fn empty() -> Self {
+        Self {
+            entries: vec![],
+            stale: true,
+            query: String::new(),
+            pick: 0,
+            matches: vec![],
+            pos: 0,
+        }
+    }
+
+    fn collect(&mut self, root: &Node) {
+        fn walk(node: &Node, path: &str, out: &mut Vec<(String, Node)>) {
+            let Ok(item) = node.try_borrow() else {
+                return;
+            };
+
+            if !item.accessible() {
+                return;
+            }
+
+            for child in item.children() {
+                let Ok(c) = child.try_borrow() else {
+                    continue;
+                };
+
+                let label = if path.is_empty() {
+                    c.label()
+                } else {
+                    format!("{}/{}", path, c.label())
+                };
+
+                if c.actionable() {
+                    out.push((label.clone(), child.clone()));
+                }
+
+                drop(c);
+                walk(&undecorated(child), &label, out);
+            }
+        }
+
+        self.entries.clear();
+        walk(root, "", &mut self.entries);
+        self.stale = false;
+        self.filter();
+    }
+
+    fn filter(&mut self) {
+        let mut matches: Vec<(usize, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (label, _))| fuzzy_score(&self.query, label).map(|s| (s, i)))
+            .collect();
+        matches.sort_by_key(|&(score, i)| (score, self.entries[i].0.len()));
+
+        self.matches = matches.into_iter().map(|(_, i)| i).collect();
+        self.pos = 0;
+    }
+}
+
+impl MenuItem for Palette {
+    fn render(&self) -> String {
+        let pick = match PALETTE_CHARS[self.pick] {
+            b' ' => '_',
+            c => c as char,
+        };
+
+        match self.matches.get(self.pos) {
+            Some(&i) => format!(
+                "{}[{}]: {} ({}/{})",
+                self.query,
+                pick,
+                self.entries[i].0,
+                self.pos + 1,
+                self.matches.len()
+            ),
+            None => format!("{}[{}]: -", self.query, pick),
+        }
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        self.control_ctx(input, stack, &mut MenuContext::detached())
+    }
+
+    fn control_ctx(&mut self, input: State, stack: &mut Vec<Node>, ctx: &mut MenuContext) -> bool {
+        use wut::gamepad::Button as B;
+
+        if self.stale {
+            let root = stack[0].clone();
+            self.collect(&root);
+        }
+
+        let t = input.trigger;
+
+        if t.contains(B::B) {
+            if stack.len() > 1 {
+                stack.pop();
+            }
+        } else if t.contains(B::A) {
+            if let Some(&i) = self.matches.get(self.pos) {
+                let node = &self.entries[i].1;
+                if !watchdog::is_faulted(node) {
+                    watchdog::run(node, || {
+                        if let Ok(mut item) = node.try_borrow_mut() {
+                            item.activate_ctx(ctx);
+                        }
+                    });
+                }
+                if stack.len() > 1 {
+                    stack.pop();
+                }
+            }
+        } else if t.contains(B::Up) {
+            self.pick = (self.pick + 1) % PALETTE_CHARS.len();
+        } else if t.contains(B::Down) {
+            self.pick = (self.pick + PALETTE_CHARS.len() - 1) % PALETTE_CHARS.len();
+        } else if t.contains(B::X) {
+            self.query.push(PALETTE_CHARS[self.pick] as char);
+            self.filter();
+        } else if t.contains(B::Y) {
+            self.query.pop();
+            self.filter();
+        } else if t.contains(B::Left) && !self.matches.is_empty() {
+            self.pos = (self.pos + self.matches.len() - 1) % self.matches.len();
+        } else if t.contains(B::Right) && !self.matches.is_empty() {
+            self.pos = (self.pos + 1) % self.matches.len();
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn focus(&mut self) {
+        self.stale = true;
+        self.query.clear();
+    }
+
+    fn blur(&mut self) {
+        self.entries.clear();
+        self.matches.clear();
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+}
+
+// endregion
+
+// region: Rating
+
+/// Row of stars. A starts editing, Left/Right changes the count, A confirms and invokes the
+/// callback, B discards the change.
+pub struct Rating {
+    text: Arc<str>,
+    enabled: bool,
+    value: u8,
+    max: u8,
+    editing: Option<u8>,
+    f: Box<dyn Fn(u8)>,
+}
+
+impl Rating {
+    pub fn new<F>(text: &str, value: u8, max: u8, f: F) -> Node
+    where
+        F: 'static + Fn(u8),
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            value: value.min(max),
+            max,
+            editing: None,
+            f: Box::new(f),
+        })))
+    }
+
+    /// Like [Rating::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, value: u8, max: u8, f: F) -> Node
+    where
+        F: 'static + Fn(u8, &mut MenuContext),
+    {
+        Contextual::new(|slot| {
+            Self::new(text, value, max, move |value| {
+                slot.with(|ctx| f(value, ctx))
+            })
+        })
+    }
+}
+
+impl MenuItem for Rating {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let theme = theme::current();
+        let count = self.editing.unwrap_or(self.value);
+
+        let _ = write!(out, "{}: ", self.text);
+        for i in 0..self.max {
+            let _ = out.write_str(if i < count {
+                theme.star_filled
+            } else {
+                theme.star_empty
+            });
+        }
+
+        if self.editing.is_some() {
+            let _ = write!(out, " {}", theme.prev_next);
+        }
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+
+        let Some(count) = self.editing else {
+            return false;
+        };
+
+        if input.trigger.contains(B::A) {
+            self.value = count;
+            self.editing = None;
+            (self.f)(self.value);
+            stack.pop();
+        } else if input.trigger.contains(B::B) {
+            self.editing = None;
+            stack.pop();
+        } else if input.trigger.contains(B::Left) && count > 0 {
+            self.editing = Some(count - 1);
+        } else if input.trigger.contains(B::Right) && count < self.max {
+            self.editing = Some(count + 1);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn focus(&mut self) {
+        self.editing = Some(self.value);
+    }
+
+    fn blur(&mut self) {
+        self.editing = None;
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Int(self.value as i64))
+    }
+
+    fn set_value(&mut self, value: &Value) -> bool {
+        let Value::Int(value) = value else {
+            return false;
+        };
+
+        self.value = (*value).clamp(0, self.max as i64) as u8;
+        (self.f)(self.value);
+        true
+    }
+}
+
+// endregion
+
+// region: Seed
+
+/// 64 bit value shown as 16 hex digits. A starts editing, Left/Right selects a digit and Up/Down
+/// changes it, A confirms and B discards. Y randomizes the value, X writes it to the log.
+pub struct Seed {
+    text: Arc<str>,
+    enabled: bool,
+    value: u64,
+    editing: Option<(u64, u32)>,
+    entropy: u64,
+    f: Box<dyn Fn(u64)>,
+}
+
+impl Seed {
+    pub fn new<F>(text: &str, value: u64, f: F) -> Node
+    where
+        F: 'static + Fn(u64),
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            value,
+            editing: None,
+            entropy: 0x9E37_79B9_7F4A_7C15,
+            f: Box::new(f),
+        })))
+    }
+
+    /// Like [Seed::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, value: u64, f: F) -> Node
+    where
+        F: 'static + Fn(u64, &mut MenuContext),
+    {
+        Contextual::new(|slot| Self::new(text, value, move |value| slot.with(|ctx| f(value, ctx))))
+    }
+
+    /// xorshift64* over the frame counter mixed in by `tick`.
+    fn random(&mut self) -> u64 {
+        let mut x = self.entropy;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.entropy = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+impl MenuItem for Seed {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let _ = write!(out, "{}: ", self.text);
+        match self.editing {
+            Some((value, digit)) => {
+                for i in (0..16).rev() {
+                    let nibble = (value >> (i * 4)) & 0xF;
+                    let _ = if i == digit as usize {
+                        write!(out, "[{:X}]", nibble)
+                    } else {
+                        write!(out, "{:X}", nibble)
+                    };
+                }
+            }
+            None => {
+                let _ = write!(out, "{:016X}", self.value);
+            }
+        }
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        let t = input.trigger;
+
+        let Some((value, digit)) = self.editing else {
+            if t.contains(B::Y) {
+                self.value = self.random();
+                (self.f)(self.value);
+                return true;
+            }
+            if t.contains(B::X) {
+                wut::println!("{}: {:016X}", self.text, self.value);
+            }
+            return false;
+        };
+
+        let shift = digit * 4;
+        let nibble = (value >> shift) & 0xF;
+
+        if t.contains(B::A) {
+            self.value = value;
+            self.editing = None;
+            (self.f)(self.value);
+            stack.pop();
+        } else if t.contains(B::B) {
+            self.editing = None;
+            stack.pop();
+        } else if t.contains(B::Up) {
+            let nibble = (nibble + 1) & 0xF;
+            self.editing = Some(((value & !(0xF << shift)) | (nibble << shift), digit));
+        } else if t.contains(B::Down) {
+            let nibble = nibble.wrapping_sub(1) & 0xF;
+            self.editing = Some(((value & !(0xF << shift)) | (nibble << shift), digit));
+        } else if t.contains(B::Left) {
+            self.editing = Some((value, (digit + 1).min(15)));
+        } else if t.contains(B::Right) {
+            self.editing = Some((value, digit.saturating_sub(1)));
+        } else if t.contains(B::Y) {
+            let random = self.random();
+            self.editing = Some((random, digit));
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        self.entropy = self.entropy.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        false
+    }
+
+    fn focus(&mut self) {
+        self.editing = Some((self.value, 15));
+    }
+
+    fn blur(&mut self) {
+        self.editing = None;
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Int(self.value as i64))
+    }
+
+    fn set_value(&mut self, value: &Value) -> bool {
+        let Value::Int(value) = value else {
+            return false;
+        };
+
+        self.value = *value as u64;
+        (self.f)(self.value);
+        true
+    }
+}
+
+// endregion
+
+// region: ColorPicker
+
+/// Color as `0xRRGGBBAA`, shown in hex with [theme::Theme::swatch]. A starts editing, Left/Right
+/// selects a channel and Up/Down changes it, by 16 while ZR is held, passing each change to the
+/// callback. A confirms and B goes back to the color from before editing.
+pub struct ColorPicker {
+    text: Arc<str>,
+    enabled: bool,
+    value: u32,
+    /// Color being edited and the selected channel, 0 being red.
+    editing: Option<(u32, u32)>,
+    f: Box<dyn Fn(u32)>,
+}
+
+impl ColorPicker {
+    pub fn new<F>(text: &str, value: u32, f: F) -> Node
+    where
+        F: 'static + Fn(u32),
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            value,
+            editing: None,
+            f: Box::new(f),
+        })))
+    }
+
+    /// Like [ColorPicker::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, value: u32, f: F) -> Node
+    where
+        F: 'static + Fn(u32, &mut MenuContext),
+    {
+        Contextual::new(|slot| Self::new(text, value, move |value| slot.with(|ctx| f(value, ctx))))
+    }
+}
+
+impl MenuItem for ColorPicker {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let swatch = theme::current().swatch;
+        let _ = write!(out, "{}: #", self.text);
+        match self.editing {
+            Some((value, channel)) => {
+                for i in 0..4 {
+                    let byte = (value >> (24 - i * 8)) & 0xFF;
+                    let _ = if i == channel as usize {
+                        write!(out, "[{:02X}]", byte)
+                    } else {
+                        write!(out, "{:02X}", byte)
+                    };
+                }
+                let _ = write!(
+                    out,
+                    " {} {}",
+                    swatch,
+                    ["R", "G", "B", "A"][channel as usize]
+                );
+            }
+            None => {
+                let _ = write!(out, "{:08X} {}", self.value, swatch);
+            }
+        }
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        let t = input.trigger;
+
+        let Some((value, channel)) = self.editing else {
+            return false;
+        };
+
+        let shift = (3 - channel) * 8;
+        let level = (value >> shift) & 0xFF;
+        let step = if input.hold.contains(B::ZR) { 16 } else { 1 };
+        let adjust = |level: u32| {
+            let changed = (value & !(0xFF << shift)) | (level << shift);
+            if changed != value {
+                (self.f)(changed);
+            }
+            Some((changed, channel))
+        };
+
+        if t.contains(B::A) {
+            self.value = value;
+            self.editing = None;
+            stack.pop();
+        } else if t.contains(B::B) {
+            if value != self.value {
+                (self.f)(self.value);
+            }
+            self.editing = None;
+            stack.pop();
+        } else if t.contains(B::Up) {
+            self.editing = adjust((level + step).min(0xFF));
+        } else if t.contains(B::Down) {
+            self.editing = adjust(level.saturating_sub(step));
+        } else if t.contains(B::Left) {
+            self.editing = Some((value, channel.saturating_sub(1)));
+        } else if t.contains(B::Right) {
+            self.editing = Some((value, (channel + 1).min(3)));
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn focus(&mut self) {
+        self.editing = Some((self.value, 0));
+    }
+
+    fn blur(&mut self) {
+        self.editing = None;
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Int(self.value as i64))
+    }
+
+    fn set_value(&mut self, value: &Value) -> bool {
+        let Value::Int(value) = value else {
+            return false;
+        };
+
+        self.value = *value as u32;
+        (self.f)(self.value);
+        true
+    }
+}
+
+// endregion
+
+// region: TextInput
+
+/// Rows of the [TextInput] keyboard.
+const KEYBOARD: [&str; 6] = [
+    "abcdefghijklm",
+    "nopqrstuvwxyz",
+    "ABCDEFGHIJKLM",
+    "NOPQRSTUVWXYZ",
+    "0123456789.-_",
+    " !?:/@#*+=()'",
+];
+
+/// Free-form text entered on a character grid. A starts editing, the D-pad moves over the
+/// keyboard row shown after the text, A types the selected character, B deletes the last one
+/// (or discards the edit when empty) and X confirms and invokes the callback.
+pub struct TextInput {
+    text: Arc<str>,
+    enabled: bool,
+    value: String,
+    max: usize,
+    editing: Option<String>,
+    cursor: (usize, usize),
+    f: Box<dyn Fn(&str)>,
+}
+
+impl TextInput {
+    pub fn new<F>(text: &str, value: &str, max: usize, f: F) -> Node
+    where
+        F: 'static + Fn(&str),
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            value: String::from(value),
+            max,
+            editing: None,
+            cursor: (0, 0),
+            f: Box::new(f),
+        })))
+    }
+
+    /// Like [TextInput::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(text: &str, value: &str, max: usize, f: F) -> Node
+    where
+        F: 'static + Fn(&str, &mut MenuContext),
+    {
+        Contextual::new(|slot| {
+            Self::new(text, value, max, move |value| {
+                slot.with(|ctx| f(value, ctx))
+            })
+        })
+    }
+
+    fn selected(&self) -> char {
+        let (row, col) = self.cursor;
+        KEYBOARD[row].as_bytes()[col] as char
+    }
+}
+
+impl MenuItem for TextInput {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let Some(edit) = &self.editing else {
+            let _ = write!(out, "{}: {}", self.text, self.value);
+            return;
+        };
+
+        let (row, col) = self.cursor;
+        let keys = KEYBOARD[row];
+        let key = match self.selected() {
+            ' ' => '_',
+            c => c,
+        };
+
+        let _ = write!(
+            out,
+            "{}: {}| {}[{}]{}",
+            self.text,
+            edit,
+            &keys[..col],
+            key,
+            &keys[col + 1..]
+        );
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+        let t = input.trigger;
+
+        let Some(edit) = &mut self.editing else {
+            return false;
+        };
+
+        let (row, col) = self.cursor;
+        let cols = KEYBOARD[0].len();
+
+        if t.contains(B::X) {
+            self.value = core::mem::take(edit);
+            self.editing = None;
+            (self.f)(&self.value);
+            stack.pop();
+        } else if t.contains(B::A) {
+            if edit.chars().count() < self.max {
+                let c = self.selected();
+                if let Some(edit) = &mut self.editing {
+                    edit.push(c);
+                }
+            }
+        } else if t.contains(B::B) {
+            if edit.pop().is_none() {
+                self.editing = None;
+                stack.pop();
+            }
+        } else if t.contains(B::Up) {
+            self.cursor = ((row + KEYBOARD.len() - 1) % KEYBOARD.len(), col);
+        } else if t.contains(B::Down) {
+            self.cursor = ((row + 1) % KEYBOARD.len(), col);
+        } else if t.contains(B::Left) {
+            self.cursor = (row, (col + cols - 1) % cols);
+        } else if t.contains(B::Right) {
+            self.cursor = (row, (col + 1) % cols);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn focus(&mut self) {
+        self.editing = Some(self.value.clone());
+    }
+
+    fn blur(&mut self) {
+        self.editing = None;
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Text(self.value.clone()))
+    }
+
+    fn set_value(&mut self, value: &Value) -> bool {
+        let Value::Text(value) = value else {
+            return false;
+        };
+
+        self.value = value.chars().take(self.max).collect();
+        (self.f)(&self.value);
+        true
+    }
+}
+
+// endregion
+
+// region: StickCalibration
+
+/// Mapping of the left stick onto D-pad navigation, see [Overlay::set_analog].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    /// Deflection below which the stick counts as centered, `0.0..1.0`.
+    pub dead_zone: f32,
+    /// Factor applied to the raw deflection before the dead zone.
+    pub sensitivity: f32,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.5,
+            sensitivity: 1.0,
+        }
+    }
+}
+
+/// Raw left stick position; `(0.0, 0.0)` if the controller has no stick.
+fn left_stick(input: &State) -> (f32, f32) {
+    input
+        .left_stick
+        .map(|stick| (stick.x, stick.y))
+        .unwrap_or_default()
+}
+
+/// Live readout of the raw left stick position.
+struct StickReadout {
+    x: f32,
+    y: f32,
+}
+
+impl MenuItem for StickReadout {
+    fn render(&self) -> String {
+        format!("Stick: {:+.2} {:+.2}", self.x, self.y)
+    }
+
+    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
+        let (x, y) = left_stick(&input);
+        let changed = x != self.x || y != self.y;
+        self.x = x;
+        self.y = y;
+        changed
+    }
+}
+
+/// Prebuilt submenu showing the raw stick position and adjusting dead zone and sensitivity of a
+/// [Calibration], e.g. the one of [Overlay::calibration]. `f` is invoked with every applied
+/// change.
+pub struct StickCalibration;
+
+impl StickCalibration {
+    pub fn new<F>(name: &str, calibration: Rc<Cell<Calibration>>, f: F) -> Node
+    where
+        F: 'static + Fn(Calibration),
+    {
+        Self::new_ctx(name, calibration, move |c, _| f(c))
+    }
+
+    /// Like [StickCalibration::new] with a callback acting on the overlay, see [MenuContext].
+    pub fn new_ctx<F>(name: &str, calibration: Rc<Cell<Calibration>>, f: F) -> Node
+    where
+        F: 'static + Fn(Calibration, &mut MenuContext),
+    {
+        let f: Rc<dyn Fn(Calibration, &mut MenuContext)> = Rc::new(f);
+        let current = calibration.get();
+
+        let dead_zone = {
+            let calibration = calibration.clone();
+            let f = f.clone();
+            Number::new_ctx(
+                "Dead zone %",
+                (current.dead_zone * 100.0) as u8,
+                5,
+                0,
+                95,
+                move |value, ctx| {
+                    let mut c = calibration.get();
+                    c.dead_zone = *value as f32 / 100.0;
+                    calibration.set(c);
+                    f(c, ctx);
+                },
+            )
+        };
+
+        let sensitivity = Number::new_ctx(
+            "Sensitivity %",
+            (current.sensitivity * 100.0) as u16,
+            10,
+            10,
+            400,
+            move |value, ctx| {
+                let mut c = calibration.get();
+                c.sensitivity = *value as f32 / 100.0;
+                calibration.set(c);
+                f(c, ctx);
+            },
+        );
+
+        Menu::new(
+            name,
+            vec![
+                Rc::new(RefCell::new(Box::new(StickReadout { x: 0.0, y: 0.0 }))),
+                dead_zone,
+                sensitivity,
+            ],
+        )
+    }
+}
+
+// endregion
+
+// region: Gamma
+
+/// Value with live preview. A starts adjusting, every Up/Down step is passed to `preview`, A
+/// passes the result to `commit` and B previews the original value again.
+pub struct Gamma {
+    text: Arc<str>,
+    enabled: bool,
+    value: f32,
+    step: f32,
+    min: f32,
+    max: f32,
+    original: Option<f32>,
+    preview: Box<dyn Fn(f32)>,
+    commit: Box<dyn Fn(f32)>,
+}
+
+impl Gamma {
+    pub fn new<P, C>(
+        text: &str,
+        value: f32,
+        step: f32,
+        min: f32,
+        max: f32,
+        preview: P,
+        commit: C,
+    ) -> Node
+    where
+        P: 'static + Fn(f32),
+        C: 'static + Fn(f32),
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            value: value.clamp(min, max),
+            step,
+            min,
+            max,
+            original: None,
+            preview: Box::new(preview),
+            commit: Box::new(commit),
+        })))
+    }
+}
+
+impl MenuItem for Gamma {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let theme = theme::current();
+
+        let _ = write!(out, "{}: {:.2}", self.text, self.value);
+        if self.original.is_some() {
+            let icon = if self.value <= self.min {
+                theme.increase
+            } else if self.value >= self.max {
+                theme.decrease
+            } else {
+                theme.adjust
+            };
+            let _ = write!(out, " {}", icon);
+        }
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+
+        let Some(original) = self.original else {
+            return false;
+        };
+
+        if input.trigger.contains(B::A) {
+            self.original = None;
+            (self.commit)(self.value);
+            stack.pop();
+        } else if input.trigger.contains(B::B) {
+            self.original = None;
+            self.value = original;
+            (self.preview)(self.value);
+            stack.pop();
+        } else if input.trigger.contains(B::Up) {
+            self.value = (self.value + self.step).min(self.max);
+            (self.preview)(self.value);
+        } else if input.trigger.contains(B::Down) {
+            self.value = (self.value - self.step).max(self.min);
+            (self.preview)(self.value);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn focus(&mut self) {
+        self.original = Some(self.value);
+    }
+
+    /// Leaving without A, e.g. by closing the overlay, reverts.
+    fn blur(&mut self) {
+        if let Some(original) = self.original.take() {
+            self.value = original;
+            (self.preview)(self.value);
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Float(self.value as f64))
+    }
+
+    fn set_value(&mut self, value: &Value) -> bool {
+        let value = match value {
+            Value::Float(v) => *v as f32,
+            Value::Int(v) => *v as f32,
+            _ => return false,
+        };
+
+        self.value = value.clamp(self.min, self.max);
+        (self.commit)(self.value);
+        true
+    }
+
+    fn accepts(&self, value: &Value) -> bool {
+        matches!(value, Value::Float(_) | Value::Int(_))
+    }
+}
+
+// endregion
+
+// region: Checklist
+
+/// Entries checked off with A, saved to `storage` under `key` on every change. Shows the progress
+/// when closed; opened with A, Left/Right pick an entry and B closes it again.
+pub struct Checklist {
+    name: String,
+    key: String,
+    entries: Vec<String>,
+    checked: Vec<bool>,
+    pos: usize,
+    open: bool,
+    storage: Rc<dyn persist::Storage>,
+}
+
+impl Checklist {
+    pub fn new(name: &str, key: &str, entries: &[&str], storage: Rc<dyn persist::Storage>) -> Node {
+        let saved = storage.load(key).unwrap_or_default();
+        let checked = (0..entries.len())
+            .map(|i| saved.get(i).is_some_and(|b| *b == b'1'))
+            .collect();
+
+        Rc::new(RefCell::new(Box::new(Self {
+            name: String::from(name),
+            key: String::from(key),
+            entries: entries.iter().map(|e| String::from(*e)).collect(),
+            checked,
+            pos: 0,
+            open: false,
+            storage,
+        })))
+    }
+
+    fn done(&self) -> usize {
+        self.checked.iter().filter(|c| **c).count()
+    }
+
+    fn save(&self) {
+        let data: Vec<u8> = self
+            .checked
+            .iter()
+            .map(|c| if *c { b'1' } else { b'0' })
+            .collect();
+        let _ = self.storage.save(&self.key, &data);
+    }
+}
+
+impl MenuItem for Checklist {
+    fn render(&self) -> String {
+        let theme = theme::current();
+
+        match self.entries.get(self.pos).filter(|_| self.open) {
+            Some(entry) => format!(
+                "{}{}{} {}{}{}",
+                theme.prev,
+                theme.gap,
+                if self.checked[self.pos] {
+                    theme.checked
+                } else {
+                    theme.unchecked
+                },
+                entry,
+                theme.gap,
+                theme.next
+            ),
+            None => format!("{}: {}/{} done", self.name, self.done(), self.entries.len()),
+        }
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+
+        if !self.open {
+            return false;
+        }
+
+        if input.trigger.contains(B::B) {
+            self.blur();
+            stack.pop();
+        } else if self.entries.is_empty() {
+            return false;
+        } else if input.trigger.contains(B::A) {
+            self.checked[self.pos] = !self.checked[self.pos];
+            self.save();
+        } else if input.trigger.contains(B::Right) {
+            self.pos = (self.pos + 1) % self.entries.len();
+        } else if input.trigger.contains(B::Left) {
+            self.pos = (self.pos + self.entries.len() - 1) % self.entries.len();
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn focus(&mut self) {
+        self.open = true;
+    }
+
+    fn blur(&mut self) {
+        self.open = false;
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        self.name.clone()
+    }
+
+    fn value(&self) -> Option<Value> {
+        Some(Value::Int(self.done() as i64))
+    }
+}
+
+// endregion
+
+// region: Splits
+
+/// Writes frames as `m:ss.cc`, or `s.cc` below a minute.
+fn write_frames(out: &mut dyn Write, frames: u32) {
+    let centis = frames as u64 * 100 / FRAMES_PER_SECOND as u64;
+    let (minutes, seconds, centis) = (centis / 6000, centis / 100 % 60, centis % 100);
+    let _ = if minutes > 0 {
+        write!(out, "{}:{:02}.{:02}", minutes, seconds, centis)
+    } else {
+        write!(out, "{}.{:02}", seconds, centis)
+    };
+}
+
+struct Segment {
+    name: String,
+    best: Option<u32>,
+}
+
+/// Segment timer showing the current segment time, the delta against the best time of that
+/// segment and the name of the next split, if any. The running time is not a value, so it neither
+/// marks the menu as changed nor is saved.
+///
+/// The timer counts frames in [MenuItem::tick]. `advance` is polled every tick and splits when it
+/// returns `true`, so the host can bind it to a hotkey or a game event; A splits as well while the
+/// item is selected, X resets the run.
+pub struct Splits {
+    text: Arc<str>,
+    enabled: bool,
+    segments: Vec<Segment>,
+    current: Option<usize>,
+    frames: u32,
+    advance: Box<dyn Fn() -> bool>,
+}
+
+impl Splits {
+    pub fn new<F>(text: &str, segments: &[&str], advance: F) -> Node
+    where
+        F: 'static + Fn() -> bool,
+    {
+        Rc::new(RefCell::new(Box::new(Self {
+            text: intern::intern(text),
+            enabled: true,
+            segments: segments
+                .iter()
+                .map(|name| Segment {
+                    name: String::from(*name),
+                    best: None,
+                })
+                .collect(),
+            current: None,
+            frames: 0,
+            advance: Box::new(advance),
+        })))
+    }
+
+    /// Starts the run, or ends the current segment and starts the next one.
+    fn split(&mut self) {
+        match self.current {
+            None if !self.segments.is_empty() => self.current = Some(0),
+            None => {}
+            Some(index) => {
+                let segment = &mut self.segments[index];
+                segment.best = Some(segment.best.map_or(self.frames, |b| b.min(self.frames)));
+                self.current = (index + 1 < self.segments.len()).then_some(index + 1);
+            }
+        }
+        self.frames = 0;
+    }
+}
+
+impl MenuItem for Splits {
+    fn render(&self) -> String {
+        rendered(self)
+    }
+
+    fn render_into(&self, out: &mut dyn Write) {
+        let _ = write!(out, "{}: ", self.text);
+        let Some(index) = self.current else {
+            write_frames(out, 0);
+            return;
+        };
+        let segment = &self.segments[index];
+
+        write_frames(out, self.frames);
+        match segment.best {
+            Some(best) if self.frames >= best => {
+                let _ = out.write_str(" +");
+                write_frames(out, self.frames - best);
+            }
+            Some(best) => {
+                let _ = out.write_str(" -");
+                write_frames(out, best - self.frames);
+            }
+            None => {}
+        }
+        if let Some(next) = self.segments.get(index + 1) {
+            let _ = write!(out, " | {}", next.name);
+        }
+    }
+
+    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+
+        if input.trigger.contains(B::A) {
+            self.split();
+        } else if input.trigger.contains(B::X) {
+            self.current = None;
+            self.frames = 0;
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        self.tick_ctx(&mut MenuContext::detached())
+    }
+
+    fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+        if (self.advance)() {
+            self.split();
+            return true;
+        }
+
+        if self.current.is_some() {
+            self.frames = self.frames.saturating_add(ctx.elapsed());
+            return true;
+        }
+
+        false
+    }
+
+    fn label(&self) -> String {
+        String::from(&*self.text)
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn set_enabled(&mut self, enabled: bool) -> bool {
+        self.enabled = enabled;
+        true
+    }
+}
+
+// endregion
+
+// region: TextPage
+
+/// Long text wrapped at `width` characters. A opens it, Up/Down page through the lines and B
+/// closes it again.
+pub struct TextPage {
+    title: String,
+    lines: Vec<String>,
+    line: usize,
+    open: bool,
+}
+
+impl TextPage {
+    pub fn new(title: &str, text: &str, width: usize) -> Node {
+        Rc::new(RefCell::new(Box::new(Self {
+            title: String::from(title),
+            lines: wrap(text, width.max(1)),
+            line: 0,
+            open: false,
+        })))
+    }
+}
+
+/// Greedy word wrap; words longer than `width` are split. Paragraph breaks are kept.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut len = 0;
+
+        for word in paragraph.split_whitespace() {
+            let mut word = word;
+            loop {
+                let chars = word.chars().count();
+                if len > 0 && len + 1 + chars > width {
+                    lines.push(core::mem::take(&mut line));
+                    len = 0;
+                }
+                if chars <= width {
+                    if len > 0 {
+                        line.push(' ');
+                        len += 1;
+                    }
+                    line.push_str(word);
+                    len += chars;
+                    break;
+                }
+
+                let split = word
+                    .char_indices()
+                    .nth(width)
+                    .map_or(word.len(), |(i, _)| i);
+                lines.push(String::from(&word[..split]));
+                word = &word[split..];
+            }
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+impl MenuItem for TextPage {
+    fn render(&self) -> String {
+        if self.open {
+            format!(
+                "{} ({}/{})",
+                self.lines.get(self.line).map_or("", |l| l.as_str()),
+                self.line + 1,
+                self.lines.len()
+            )
+        } else {
+            self.summary()
+        }
+    }
+
+    fn summary(&self) -> String {
+        format!("{} {}", self.title, theme::current().submenu)
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+
+        if !self.open {
+            return false;
+        }
+
+        if input.trigger.contains(B::B) {
+            self.blur();
+            stack.pop();
+        } else if input.trigger.contains(B::Down) && self.line + 1 < self.lines.len() {
+            self.line += 1;
+        } else if input.trigger.contains(B::Up) && self.line > 0 {
+            self.line -= 1;
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn focus(&mut self) {
+        self.open = true;
+        self.line = 0;
+    }
+
+    fn blur(&mut self) {
+        self.open = false;
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        self.title.clone()
+    }
+}
+
+// endregion
+
+// region: Log
+
+/// Entries kept by the event log of an [Overlay].
+const LOG_CAPACITY: usize = 64;
+
+/// Something the [Overlay] did, see [Overlay::events].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Opened,
+    Closed,
+    /// An item was pushed onto the navigation stack.
+    Pushed(String),
+    /// An item was popped off the navigation stack.
+    Popped(String),
+    /// The value of an item changed through input.
+    Changed(String, Value),
+    /// The cursor of the innermost menu moved onto an item.
+    Moved(String),
+    Error(String),
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Event::Opened => write!(f, "opened"),
+            Event::Closed => write!(f, "closed"),
+            Event::Pushed(label) => write!(f, "pushed {}", label),
+            Event::Popped(label) => write!(f, "popped {}", label),
+            Event::Changed(label, value) => write!(f, "{} = {:?}", label, value),
+            Event::Moved(label) => write!(f, "moved to {}", label),
+            Event::Error(e) => write!(f, "error: {}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// Number of [Overlay::run] calls before the event.
+    pub frame: u64,
+    pub event: Event,
+}
+
+type Log = Rc<RefCell<VecDeque<LogEntry>>>;
+
+/// Shows the event log of an [Overlay], newest first. A opens it, Up/Down scroll and B closes it.
+pub struct LogView {
+    name: String,
+    log: Log,
+    index: usize,
+    open: bool,
+}
+
+impl MenuItem for LogView {
+    fn render(&self) -> String {
+        let log = self.log.borrow();
+
+        if !self.open {
+            return format!("{} ({})", self.name, log.len());
+        }
+
+        match log.iter().rev().nth(self.index) {
+            Some(entry) => format!(
+                "#{} {} ({}/{})",
+                entry.frame,
+                entry.event,
+                self.index + 1,
+                log.len()
+            ),
+            None => String::from("(empty)"),
+        }
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+
+        if !self.open {
+            return false;
+        }
+
+        let len = self.log.borrow().len();
+        if input.trigger.contains(B::B) {
+            self.blur();
+            stack.pop();
+        } else if input.trigger.contains(B::Down) && self.index + 1 < len {
+            self.index += 1;
+        } else if input.trigger.contains(B::Up) && self.index > 0 {
+            self.index -= 1;
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn focus(&mut self) {
+        self.open = true;
+        self.index = 0;
+    }
+
+    fn blur(&mut self) {
+        self.open = false;
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        self.name.clone()
+    }
+}
+
+/// Bytes a [Recorder] keeps at most; later events are dropped.
+const RECORDING_LIMIT: usize = 64 * 1024;
+
+/// Opt-in recording of a menu session for UX analysis, see [Overlay::set_recorder].
+///
+/// Every event goes into one line, `frame<TAB>microseconds<TAB>event`, with `-` for the time
+/// without [Overlay::set_clock]. The lines are written to `storage` under `key` whenever the
+/// overlay closes and by [Overlay::flush_recording], so the host decides where they end up,
+/// e.g. a file on the SD card.
+pub struct Recorder {
+    storage: Rc<dyn persist::Storage>,
+    key: String,
+    /// Whether changed values are written or only which item changed.
+    values: bool,
+    /// Whether errors from callbacks are written; they may contain user data.
+    errors: bool,
+    lines: RefCell<String>,
+}
+
+impl Recorder {
+    /// Values and errors are left out unless enabled with `values` and `errors`.
+    pub fn new(storage: Rc<dyn persist::Storage>, key: &str, values: bool, errors: bool) -> Self {
+        Self {
+            storage,
+            key: String::from(key),
+            values,
+            errors,
+            lines: RefCell::new(String::new()),
+        }
+    }
+
+    fn record(&self, frame: u64, time: Option<u64>, event: &Event) {
+        let mut lines = self.lines.borrow_mut();
+        if lines.len() >= RECORDING_LIMIT {
+            return;
+        }
+
+        let _ = write!(lines, "{}\t", frame);
+        let _ = match time {
+            Some(time) => write!(lines, "{}\t", time),
+            None => write!(lines, "-\t"),
+        };
+        let _ = match event {
+            Event::Changed(label, _) if !self.values => writeln!(lines, "{} changed", label),
+            Event::Error(_) if !self.errors => writeln!(lines, "error"),
+            event => writeln!(lines, "{}", event),
+        };
+    }
+
+    fn flush(&self) {
+        let _ = self.storage.save(&self.key, self.lines.borrow().as_bytes());
+    }
+}
+
+// endregion
+
+// region: Error
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The renderer could not show the HUD, e.g. the notification could not be created.
+    Show(String),
+    /// The renderer failed to draw a text update.
+    Text(String),
+    /// Values of [Overlay::set_autosave] or [Overlay::set_save_prompt] could not be written.
+    Save(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Show(e) => write!(f, "failed to show overlay: {}", e),
+            Self::Text(e) => write!(f, "failed to update overlay: {}", e),
+            Self::Save(e) => write!(f, "failed to save values: {}", e),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+// endregion
+
+// region: Context
+
+/// Change requested through a [MenuContext], carried out once the input is handled.
+enum Request {
+    Navigate(String),
+    Push(Node),
+    Pop,
+    Close,
+    Notify(String),
+}
+
+/// Handle for callbacks to act on the [Overlay] running them, passed to the callbacks of items
+/// built with a `_ctx` constructor, e.g. [Button::new_ctx]. Navigation, closing and notices take
+/// effect once the input or tick is handled.
+///
+/// Callbacks running outside of the overlay, e.g. when the host calls [MenuItem::set_value],
+/// get a detached context: lookups find nothing and requests are dropped.
+pub struct MenuContext {
+    root: Option<Node>,
+    requests: Vec<Request>,
+    elapsed: u32,
+    timings: timings::Timings,
+}
+
+impl MenuContext {
+    fn new(root: Node, elapsed: u32, timings: timings::Timings) -> Self {
+        Self {
+            root: Some(root),
+            requests: vec![],
+            elapsed,
+            timings,
+        }
+    }
+
+    fn detached() -> Self {
+        Self {
+            root: None,
+            requests: vec![],
+            elapsed: 1,
+            timings: timings::Timings::DEFAULT,
+        }
+    }
+
+    /// Whether the context belongs to an overlay, see [MenuContext].
+    pub fn is_attached(&self) -> bool {
+        self.root.is_some()
+    }
+
+    /// 60 Hz frames that passed since the overlay's previous update. Always `1` unless the
+    /// overlay has a clock, see [Overlay::set_clock], in which case it is the measured time and
+    /// may be `0` or more than `1` when the overlay is not updated exactly 60 times a second.
+    pub fn elapsed(&self) -> u32 {
+        self.elapsed
+    }
+
+    /// Input timings of the overlay, see [Overlay::set_timings]. [timings::Timings::DEFAULT] for
+    /// a detached context.
+    pub fn timings(&self) -> timings::Timings {
+        self.timings
+    }
+
+    /// See [find]. Items handling the input, e.g. the one whose callback runs, are skipped.
+    pub fn find(&self, id: &str) -> Option<Node> {
+        self.root.as_ref().and_then(|root| find(root, id))
+    }
+
+    /// See [find_path]. Items handling the input are skipped.
+    pub fn find_path(&self, path: &str) -> Option<Node> {
+        self.root.as_ref().and_then(|root| find_path(root, path))
+    }
+
+    /// Sets the value of the item at `path` like a change by the user. Returns whether the item
+    /// was found and accepted the value.
+    pub fn set_value(&self, path: &str, value: &Value) -> bool {
+        self.find_path(path)
+            .is_some_and(|node| watchdog::set_value(&node, value))
+    }
+
+    /// Opens the item at `path`, see [Overlay::navigate].
+    pub fn navigate(&mut self, path: &str) {
+        self.requests.push(Request::Navigate(String::from(path)));
+    }
+
+    /// Pushes `node` onto the navigation stack, e.g. a submenu built on the fly.
+    pub fn push(&mut self, node: Node) {
+        self.requests.push(Request::Push(node));
+    }
+
+    /// Pops the navigation head, unless it is the root.
+    pub fn pop(&mut self) {
+        self.requests.push(Request::Pop);
+    }
+
+    /// Closes the overlay.
+    pub fn close(&mut self) {
+        self.requests.push(Request::Close);
+    }
+
+    /// Shows `message` on the menu line for a while.
+    pub fn notify(&mut self, message: &str) {
+        self.requests.push(Request::Notify(String::from(message)));
+    }
+}
+
+/// Where a [Contextual] lends the [MenuContext] to the callback of the item it wraps.
+#[derive(Default, Clone)]
+struct ContextSlot(Rc<RefCell<Option<MenuContext>>>);
+
+impl ContextSlot {
+    /// Runs `f` with the lent context, or a detached one if there is none.
+    fn with(&self, f: impl FnOnce(&mut MenuContext)) {
+        let lent = self.0.borrow_mut().take();
+        let attached = lent.is_some();
+        let mut ctx = lent.unwrap_or_else(MenuContext::detached);
+        f(&mut ctx);
+        if attached {
+            *self.0.borrow_mut() = Some(ctx);
+        }
+    }
+
+    /// Lends `ctx` to the callback while `f` runs. `f` gets a context of its own for nested
+    /// items, whose requests follow those of the callback.
+    fn lend<R>(&self, ctx: &mut MenuContext, f: impl FnOnce(&mut MenuContext) -> R) -> R {
+        let nested = MenuContext {
+            root: ctx.root.clone(),
+            requests: vec![],
+            elapsed: ctx.elapsed,
+            timings: ctx.timings,
+        };
+        *self.0.borrow_mut() = Some(core::mem::replace(ctx, nested));
+
+        let result = f(ctx);
+
+        if let Some(mut lent) = self.0.borrow_mut().take() {
+            lent.requests.append(&mut ctx.requests);
+            *ctx = lent;
+        }
+        result
+    }
+}
+
+/// Item built by a `_ctx` constructor: behaves like the wrapped item and lends the
+/// [MenuContext] to its callback while it handles input or ticks.
+struct Contextual {
+    slot: ContextSlot,
+    inner: Node,
+}
+
+impl Contextual {
+    /// Wraps the item built by `build` with a callback going through the given slot.
+    fn new(build: impl FnOnce(ContextSlot) -> Node) -> Node {
+        let slot = ContextSlot::default();
+        let inner = build(slot.clone());
+        Rc::new(RefCell::new(Box::new(Self { slot, inner })))
+    }
+}
+
+impl MenuItem for Contextual {
+    forward_menu_item!(
+        render,
+        render_into,
+        render_bytes,
+        render_decorated,
+        control,
+        tick,
+        focus,
+        blur,
+        owns,
+        inline,
+        children,
+        decorates,
+        summary,
+        select,
+        accessible,
+        actionable,
+        activate,
+        label,
+        enabled,
+        set_enabled,
+        help,
+        sounds,
+        default_value,
+        take_error,
+        preview,
+        current,
+        select_entry,
+        value,
+        set_value,
+        accepts,
+        focusable,
+        interactive,
+        enters,
+    );
+
+    fn control_ctx(&mut self, input: State, stack: &mut Vec<Node>, ctx: &mut MenuContext) -> bool {
+        let inner = &self.inner;
+        self.slot
+            .lend(ctx, |ctx| inner.borrow_mut().control_ctx(input, stack, ctx))
+    }
+
+    fn tick_ctx(&mut self, ctx: &mut MenuContext) -> bool {
+        let inner = &self.inner;
+        self.slot.lend(ctx, |ctx| inner.borrow_mut().tick_ctx(ctx))
+    }
+
+    fn activate_ctx(&mut self, ctx: &mut MenuContext) {
+        let inner = &self.inner;
+        self.slot
+            .lend(ctx, |ctx| inner.borrow_mut().activate_ctx(ctx));
+    }
+}
+
+// endregion
+
+// region: Root
+
+/// Default of [Overlay::set_list_window].
+const LIST_WINDOW: usize = 10;
+
+/// Consecutive `show()` failures after which the fallback sink is used.
+const SHOW_RETRY_LIMIT: u32 = 4;
+/// Upper bound for the retry backoff in frames.
+const SHOW_BACKOFF_MAX: u32 = 64;
+/// Frames an error toast stays visible.
+const TOAST_FRAMES: u32 = 3 * FRAMES_PER_SECOND;
+/// Frames the confirmation of a reset stays visible.
+const FLASH_FRAMES: u32 = FRAMES_PER_SECOND / 2;
+/// Assumed call rate of [Overlay::run] for frame based timings.
+const FRAMES_PER_SECOND: u32 = 60;
+/// The auto-hide countdown is shown for the last this many frames.
+const COUNTDOWN_FRAMES: u32 = 3 * FRAMES_PER_SECOND;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The overlay is open while the combo is held.
+    Hold,
+    /// Pressing the combo opens the overlay, pressing it again closes it.
+    Toggle,
+}
+
+/// What the HUD shows after a frame of input.
+enum Update {
+    Hide,
+    /// The hold delay progress bar.
+    Indicator,
+    /// The menu, rendered again if `true`.
+    Render(bool),
+}
+
+pub struct Overlay {
+    renderer: Box<dyn render::Renderer>,
+    /// Whether [render::Renderer::show] succeeded and nothing hid the HUD since.
+    shown: bool,
+    root: Node,
+    stack: Vec<Node>,
+    error: Option<Error>,
+    failures: u32,
+    retry_in: u32,
+    fallback: Box<dyn Fn(&str)>,
+    close_on_back: bool,
+    dismissed: bool,
+    mode: Mode,
+    open: bool,
+    combo_frames: u32,
+    hold_delay: u32,
+    hold_indicator: bool,
+    idle: u32,
+    auto_hide: Option<u32>,
+    countdown: Option<u32>,
+    palette_combo: Option<FlagSet<wut::gamepad::Button>>,
+    clipboard: Option<Value>,
+    copy_combo: Option<FlagSet<wut::gamepad::Button>>,
+    paste_combo: Option<FlagSet<wut::gamepad::Button>>,
+    /// Whether holding X resets the selected item, see [Overlay::set_reset_hold].
+    reset_hold: bool,
+    timings: timings::Timings,
+    reset_held: u32,
+    flash: u32,
+    flash_text: String,
+    inline_toasts: bool,
+    log: Log,
+    frame: u64,
+    clock: Option<Box<dyn Fn() -> u64>>,
+    /// Opacity of the HUD backdrop in percent, shared with [Overlay::settings].
+    opacity: Rc<Cell<u8>>,
+    /// Opacity last passed to the renderer.
+    drawn_opacity: Option<u8>,
+    /// Last clock reading and the microseconds not yet counted as a frame.
+    time: Option<(u64, u64)>,
+    /// Frames that passed before the current update, see [MenuContext::elapsed].
+    elapsed: u32,
+    #[cfg(feature = "notifications")]
+    toasts: Vec<(notifications::Notification, u32)>,
+    analog: bool,
+    calibration: Rc<Cell<Calibration>>,
+    stick: FlagSet<wut::gamepad::Button>,
+    combo: FlagSet<wut::gamepad::Button>,
+    save_prompt: Option<(Rc<dyn persist::Storage>, String)>,
+    /// Values when the overlay was opened, to tell whether anything changed.
+    baseline: persist::StateBlob,
+    /// Answer of the open [ClosePrompt] and whether closing dismisses the overlay.
+    prompt: Option<(Rc<Cell<Option<Closing>>>, bool)>,
+    recorder: Option<Recorder>,
+    /// Whether the overlay was open at the start of the last frame.
+    capturing: bool,
+    consumed: FlagSet<wut::gamepad::Button>,
+    overrides: Vec<Override>,
+    /// Override state of the selected item when last rendered.
+    overridden: Option<Option<u32>>,
+    /// Microseconds a callback may take before the [watchdog] disables its item.
+    callback_budget: Option<u64>,
+    /// Combos opening the overlay at a path, see [Overlay::add_shortcut].
+    shortcuts: Vec<(FlagSet<wut::gamepad::Button>, String)>,
+    /// Shortcut combo that opened the overlay in [Mode::Hold], kept open while it is held.
+    shortcut: Option<FlagSet<wut::gamepad::Button>>,
+    autosave: Option<Autosave>,
+    /// Switch of [Overlay::set_autosave] in [Overlay::settings].
+    autosave_enabled: Rc<Cell<bool>>,
+    breadcrumb: Option<Breadcrumb>,
+    /// Entries shown at once by [Detail::List] outputs.
+    list_window: usize,
+    /// Frames the cursor rests on an item before its help is shown.
+    help_delay: Option<u32>,
+    /// Entry the cursor is on and for how many frames.
+    hover: (Option<Weak<RefCell<Box<dyn MenuItem>>>>, u32),
+    /// Read-only outputs besides the HUD, see [Overlay::add_target].
+    targets: Vec<Target>,
+    /// Marker of a running session, see [OverlayBuilder::safe_mode].
+    sentinel: Option<(Rc<dyn persist::Storage>, String)>,
+    safe_mode: Option<SafeMode>,
+}
+
+impl Overlay {
+    /// Overlay shown as a dynamic notification.
+    #[cfg(feature = "notifications")]
+    pub fn new(root: Node) -> Self {
+        Self::with_renderer(root, Box::new(render::Notification::new()))
+    }
+
+    fn with_renderer(root: Node, renderer: Box<dyn render::Renderer>) -> Self {
+        let mut r = Self {
+            renderer,
+            shown: false,
+            root,
+            stack: vec![],
+            error: None,
+            failures: 0,
+            retry_in: 0,
+            #[cfg(feature = "osscreen")]
+            fallback: Box::new(osscreen::sink()),
+            #[cfg(not(feature = "osscreen"))]
+            fallback: Box::new(|line| wut::println!("[overlay] {}", line)),
+            close_on_back: false,
+            dismissed: false,
+            mode: Mode::Hold,
+            open: false,
+            combo_frames: 0,
+            hold_delay: 0,
+            hold_indicator: true,
+            idle: 0,
+            auto_hide: None,
+            countdown: None,
+            palette_combo: None,
+            clipboard: None,
+            copy_combo: None,
+            paste_combo: None,
+            reset_hold: false,
+            timings: timings::Timings::DEFAULT,
+            reset_held: 0,
+            flash: 0,
+            flash_text: String::new(),
+            inline_toasts: false,
+            log: Rc::new(RefCell::new(VecDeque::with_capacity(LOG_CAPACITY))),
+            frame: 0,
+            clock: None,
+            opacity: Rc::new(Cell::new(100)),
+            drawn_opacity: None,
+            time: None,
+            elapsed: 1,
+            #[cfg(feature = "notifications")]
+            toasts: vec![],
+            analog: false,
+            calibration: Rc::new(Cell::new(Calibration::default())),
+            stick: FlagSet::default(),
+            combo: wut::gamepad::Button::L | wut::gamepad::Button::R,
+            save_prompt: None,
+            baseline: persist::StateBlob::default(),
+            prompt: None,
+            recorder: None,
+            capturing: false,
+            consumed: FlagSet::default(),
+            overrides: vec![],
+            overridden: None,
+            callback_budget: None,
+            shortcuts: vec![],
+            shortcut: None,
+            autosave: None,
+            autosave_enabled: Rc::new(Cell::new(true)),
+            breadcrumb: None,
+            list_window: LIST_WINDOW,
+            help_delay: Some(0),
+            hover: (None, 0),
+            targets: vec![],
+            sentinel: None,
+            safe_mode: None,
+        };
+
+        r.stack.push(r.root.clone());
+        r.root.borrow_mut().focus();
+
+        r
+    }
+
+    pub fn control(&mut self, input: State) -> bool {
+        use wut::gamepad::Button as B;
+        let input = self.analog(input);
+
+        let mut changed = self.revalidate();
+
+        if self.close_on_back && self.stack.len() == 1 && input.trigger.contains(B::B) {
+            self.close(true);
+            return self.open;
+        }
+
+        // A faulted item that has focus gets no more input either.
+        if self.stack.len() > 1 && watchdog::is_faulted(self.stack.last().unwrap()) {
+            let node = self.stack.pop().unwrap();
+            node.borrow_mut().blur();
+            self.log(Event::Popped(node.borrow().label()));
+            return true;
+        }
+
+        let before = self.stack.clone();
+        let target = self
+            .selected()
+            .unwrap_or_else(|| before.last().unwrap().clone());
+        let value = target.borrow().value();
+
+        let head = self.stack.last().unwrap().clone();
+        let start = self.clock.as_ref().map(|clock| clock());
+        let mut ctx = MenuContext::new(self.root.clone(), self.elapsed, self.timings);
+        changed |= watchdog::run(&target, || {
+            head.borrow_mut()
+                .control_ctx(input, &mut self.stack, &mut ctx)
+        });
+        if let (Some(budget), Some(start), Some(clock)) = (self.callback_budget, start, &self.clock)
+        {
+            let took = clock().saturating_sub(start);
+            if took > budget && !watchdog::is_faulted(&target) {
+                watchdog::fault(&target, &format!("took {} us", took));
+                self.log(Event::Error(format!(
+                    "{} disabled",
+                    target.borrow().label()
+                )));
+                changed = true;
+            }
+        }
+
+        let kept = before
+            .iter()
+            .zip(&self.stack)
+            .take_while(|(a, b)| Rc::ptr_eq(a, b))
+            .count();
+        for node in before[kept..].iter().rev() {
+            self.log(Event::Popped(node.borrow().label()));
+        }
+        for node in &self.stack[kept..] {
+            self.log(Event::Pushed(node.borrow().label()));
+            if events::subscribed() && !node.borrow().children().is_empty() {
+                events::emit(events::Event::MenuEntered {
+                    id: events::id(node),
+                });
+            }
+        }
+
+        let new = target.borrow().value();
+        let activated = input.trigger.contains(B::A) && target.borrow().actionable();
+        let mut confirmed = kept < self.stack.len() || activated;
+        if activated && events::subscribed() {
+            events::emit(events::Event::Activated {
+                id: events::id(&target),
+            });
+        }
+        if let Some(new) = new.filter(|new| Some(new) != value.as_ref()) {
+            let label = target.borrow().label();
+            if events::subscribed() {
+                events::emit(events::Event::ValueChanged {
+                    id: events::id(&target),
+                    value: new.clone(),
+                });
+            }
+            self.log(Event::Changed(label, new));
+            confirmed = true;
+        }
+        let mut cue = confirmed.then(|| (sound::Cue::Confirm, target.borrow().sounds()));
+
+        if let Some(selected) = self.selected().filter(|s| !Rc::ptr_eq(s, &target)) {
+            let label = selected.borrow().label();
+            self.log(Event::Moved(label));
+            cue = cue.or_else(|| Some((sound::Cue::Navigate, selected.borrow().sounds())));
+        }
+
+        changed |= self.carry_out(ctx.requests);
+
+        let head = before.last().unwrap();
+        let mut errors = vec![target.borrow_mut().take_error()];
+        if !Rc::ptr_eq(head, &target) {
+            errors.push(head.borrow_mut().take_error());
+        }
+        for error in errors.into_iter().flatten() {
+            self.toast(error);
+            cue = Some((sound::Cue::Error, target.borrow().sounds()));
+        }
+
+        if let Some((cue, sounds)) = cue {
+            sound::play_from(cue, sounds);
+        }
+
+        changed
+    }
+
+    /// Carries out what callbacks requested through their [MenuContext]. Returns whether there
+    /// was anything.
+    fn carry_out(&mut self, requests: Vec<Request>) -> bool {
+        let any = !requests.is_empty();
+        for request in requests {
+            match request {
+                Request::Navigate(path) => {
+                    self.navigate(&path);
+                }
+                Request::Push(node) => {
+                    node.borrow_mut().focus();
+                    self.log(Event::Pushed(node.borrow().label()));
+                    self.stack.push(node);
+                }
+                Request::Pop if self.stack.len() > 1 => {
+                    let node = self.stack.pop().unwrap();
+                    node.borrow_mut().blur();
+                    self.log(Event::Popped(node.borrow().label()));
+                }
+                Request::Pop => {}
+                Request::Close => self.close(false),
+                Request::Notify(message) => self.flash(&message, TOAST_FRAMES),
+            }
+        }
+        any
+    }
+
+    /// Shows the errors raised by any item of the tree since the last frame, e.g. by ticks,
+    /// bindings or debounced callbacks, see [MenuItem::take_error]. Returns whether there were
+    /// any.
+    fn show_errors(&mut self) -> bool {
+        fn walk(node: &Node, out: &mut Vec<String>) {
+            let Ok(mut item) = node.try_borrow_mut() else {
+                return;
+            };
+            out.extend(item.take_error());
+            for child in item.children() {
+                walk(child, out);
+            }
+        }
+
+        let mut errors = vec![];
+        walk(&self.root, &mut errors);
+        let any = !errors.is_empty();
+        for error in errors {
+            self.toast(error);
+        }
+        if any {
+            sound::play_from(sound::Cue::Error, None);
+        }
+        any
+    }
+
+    fn toast(&mut self, message: String) {
+        wut::println!("[overlay] error: {}", message);
+
+        if self.inline_toasts || cfg!(not(feature = "notifications")) {
+            self.flash(&message, TOAST_FRAMES);
+        } else {
+            #[cfg(feature = "notifications")]
+            if let Ok(toast) = notifications::dynamic(&message).show() {
+                self.toasts.push((toast, TOAST_FRAMES));
+            }
+        }
+
+        self.log(Event::Error(message));
+    }
+
+    /// Appends `text` to the menu line for `frames` frames.
+    fn flash(&mut self, text: &str, frames: u32) {
+        self.flash_text = String::from(text);
+        self.flash = frames;
+    }
+
+    /// Shows callback errors on the menu line instead of in notifications of their own.
+    ///
+    /// The notifications backend stacks notifications in the order they are created and has no
+    /// priorities, so any notification shown after the HUD, including toasts from this crate,
+    /// can push the menu line around. Inline toasts avoid adding to that.
+    pub fn set_inline_toasts(&mut self, inline: bool) {
+        self.inline_toasts = inline;
+    }
+
+    fn log(&self, event: Event) {
+        if let Some(recorder) = &self.recorder {
+            let time = self.clock.as_ref().map(|clock| clock());
+            recorder.record(self.frame, time, &event);
+            if event == Event::Closed {
+                recorder.flush();
+            }
+        }
+
+        let mut log = self.log.borrow_mut();
+        if log.len() == LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back(LogEntry {
+            frame: self.frame,
+            event,
+        });
+    }
+
+    /// Records all events of the session from now on, or stops recording with `None`.
+    pub fn set_recorder(&mut self, recorder: Option<Recorder>) {
+        self.flush_recording();
+        self.recorder = recorder;
+    }
+
+    /// Writes the events recorded so far, see [Recorder].
+    pub fn flush_recording(&self) {
+        if let Some(recorder) = &self.recorder {
+            recorder.flush();
+        }
+    }
+
+    /// Recent events, oldest first.
+    pub fn events(&self) -> Ref<'_, VecDeque<LogEntry>> {
+        self.log.borrow()
+    }
+
+    /// A [LogView] showing the events of this overlay.
+    pub fn log_view(&self, name: &str) -> Node {
+        Rc::new(RefCell::new(Box::new(LogView {
+            name: String::from(name),
+            log: self.log.clone(),
+            index: 0,
+            open: false,
+        })))
+    }
+
+    fn set_open(&mut self, open: bool) {
+        if open != self.open {
+            self.log(if open { Event::Opened } else { Event::Closed });
+            events::emit(if open {
+                events::Event::OverlayOpened
+            } else {
+                events::Event::OverlayClosed
+            });
+            if open && self.save_prompt.is_some() {
+                self.baseline = persist::StateBlob::capture(&self.root);
+            }
+            if let Some(autosave) = self.autosave.as_mut().filter(|_| !open)
+                && self.safe_mode.is_none()
+            {
+                let saved = autosave.save(&self.root, self.autosave_enabled.get());
+                self.report_save(saved);
+            }
+        }
+        self.open = open;
+    }
+
+    /// Closes the overlay, or asks whether to save first if values changed since it was opened
+    /// and [Overlay::set_save_prompt] is set.
+    fn close(&mut self, dismiss: bool) {
+        if self.prompt.is_some() {
+            return;
+        }
+
+        if self.save_prompt.is_some()
+            && self
+                .baseline
+                .differs(&persist::StateBlob::capture(&self.root))
+        {
+            let answer = Rc::new(Cell::new(None));
+            self.stack.push(Rc::new(RefCell::new(Box::new(ClosePrompt {
+                choice: Closing::Save,
+                answer: answer.clone(),
+            }))));
+            self.prompt = Some((answer, dismiss));
+            return;
+        }
+
+        if dismiss {
+            self.dismiss();
+        } else {
+            self.set_open(false);
+        }
+    }
+
+    /// Acts on the answer to the [ClosePrompt], once given.
+    fn answer_prompt(&mut self) {
+        let Some(closing) = self.prompt.as_ref().and_then(|(answer, _)| answer.get()) else {
+            return;
+        };
+        let Some((_, dismiss)) = self.prompt.take() else {
+            return;
+        };
+
+        match closing {
+            Closing::Cancel => return,
+            Closing::Save => {
+                if let Some((storage, key)) = &self.save_prompt {
+                    let saved = persist::save(&self.root, &**storage, key);
+                    self.report_save(saved);
+                }
+            }
+            Closing::Discard => {
+                core::mem::take(&mut self.baseline).apply(&self.root);
+            }
+        }
+
+        if dismiss {
+            self.dismiss();
+        } else {
+            self.set_open(false);
+        }
+    }
+
+    /// Whether the overlay started in safe mode and the full menu is not loaded yet, see
+    /// [OverlayBuilder::safe_mode].
+    pub fn in_safe_mode(&self) -> bool {
+        self.safe_mode.is_some()
+    }
+
+    /// Clears the mark of [OverlayBuilder::safe_mode], as the session ends cleanly. Call this
+    /// from the plugin's deinit: overlays kept in statics are never dropped, and without it the
+    /// next session would start in safe mode. Dropping the overlay does the same.
+    pub fn end_session(&mut self) {
+        if let Some((storage, key)) = self.sentinel.take() {
+            let _ = storage.save(&key, &[]);
+        }
+    }
+
+    /// Shows a minimal menu in place of the root until the user loads it, without restoring or
+    /// saving values. `restore` is applied, and the save prompt set up for it if `save_prompt`,
+    /// once the full menu is loaded.
+    fn enter_safe_mode(
+        &mut self,
+        restore: Option<(Rc<dyn persist::Storage>, String)>,
+        save_prompt: bool,
+    ) {
+        let load = Rc::new(Cell::new(false));
+        let safe = Menu::new(
+            "Safe mode",
+            vec![
+                Text::new(|| String::from("The last session did not end cleanly")),
+                Button::new("Load full menu", {
+                    let load = load.clone();
+                    move || load.set(true)
+                }),
+            ],
+        );
+
+        let root = core::mem::replace(&mut self.root, safe);
+        self.set_root_stack();
+        self.safe_mode = Some(SafeMode {
+            root,
+            load,
+            restore,
+            save_prompt,
+        });
+        self.log(Event::Error(String::from("started in safe mode")));
+    }
+
+    /// Leaves safe mode, restoring the values of the full menu if set up.
+    fn load_full_menu(&mut self) {
+        let Some(safe) = self.safe_mode.take() else {
+            return;
+        };
+
+        self.root = safe.root;
+        self.set_root_stack();
+        if let Some((storage, key)) = &safe.restore {
+            persist::restore(&self.root, &**storage, key);
+            if safe.save_prompt {
+                self.set_save_prompt(Some((storage.clone(), key)));
+            }
+        }
+        if let Some(autosave) = &mut self.autosave {
+            autosave.saved = persist::StateBlob::capture(&self.root);
+        }
+    }
+
+    /// Replaces the navigation stack with the root alone.
+    fn set_root_stack(&mut self) {
+        while let Some(node) = self.stack.pop() {
+            node.borrow_mut().blur();
+        }
+        self.stack.push(self.root.clone());
+        self.root.borrow_mut().focus();
+    }
+
+    /// Asks "Save changes?" with Save, Discard and Cancel when the overlay is closed after values
+    /// changed since it was opened. Save writes all values to `storage` under the key, see
+    /// [persist::restore]; Discard sets the values from when the overlay was opened again.
+    pub fn set_save_prompt(&mut self, target: Option<(Rc<dyn persist::Storage>, &str)>) {
+        self.save_prompt = target.map(|(storage, key)| (storage, String::from(key)));
+    }
+
+    /// Writes all values to `storage` under the key every `interval_ms` milliseconds and when
+    /// the overlay is closed, if they changed since the last write, so they survive a crash of
+    /// the game. Restore them with [persist::restore] or [OverlayBuilder::restore]. It can be
+    /// switched off from [Overlay::settings].
+    pub fn set_autosave(
+        &mut self,
+        target: Option<(Rc<dyn persist::Storage>, &str)>,
+        interval_ms: u32,
+    ) {
+        self.autosave = target.map(|(storage, key)| Autosave {
+            storage,
+            key: String::from(key),
+            interval: (interval_ms * FRAMES_PER_SECOND).div_ceil(1000).max(1),
+            elapsed: 0,
+            saved: persist::StateBlob::capture(&self.root),
+        });
+    }
+
+    /// Shows and keeps the error of a failed save of the overlay's own, see [Error::Save].
+    fn report_save(&mut self, saved: Result<(), String>) {
+        if let Err(e) = saved {
+            self.toast(format!("save failed: {}", e));
+            self.error = Some(Error::Save(e));
+        }
+    }
+
+    fn set_error(&mut self, error: Error) {
+        self.log(Event::Error(format!("{}", error)));
+        self.error = Some(error);
+    }
+
+    /// Adds D-pad presses for the left stick if analog navigation is enabled.
+    fn analog(&mut self, mut input: State) -> State {
+        use wut::gamepad::Button as B;
+
+        if !self.analog {
+            return input;
+        }
+
+        let c = self.calibration.get();
+        let (x, y) = left_stick(&input);
+        let (x, y) = (x * c.sensitivity, y * c.sensitivity);
+
+        let mut directions = FlagSet::<B>::default();
+        if x < -c.dead_zone {
+            directions |= B::Left;
+        } else if x > c.dead_zone {
+            directions |= B::Right;
+        }
+        if y > c.dead_zone {
+            directions |= B::Up;
+        } else if y < -c.dead_zone {
+            directions |= B::Down;
+        }
+
+        input.hold |= directions;
+        input.trigger |= directions - self.stick;
+        self.stick = directions;
+        input
+    }
+
+    /// Pops stack entries whose nodes are no longer owned by their parent.
+    fn revalidate(&mut self) -> bool {
+        let Some(end) =
+            (1..self.stack.len()).find(|&i| !self.stack[i - 1].borrow().owns(&self.stack[i]))
+        else {
+            return false;
+        };
+
+        for node in self.stack.drain(end..).rev() {
+            node.borrow_mut().blur();
+        }
+
+        true
+    }
+
+    /// Moves to the item at `path`, see [find_path]: the stack is reset to the root, every item on
+    /// the way is selected in its parent and entered, the last one only if it can be entered.
+    /// Returns whether the whole path was found; otherwise the navigation stops at the last item
+    /// found. It also stops before disabled items and those disabled by the [watchdog].
+    pub fn navigate(&mut self, path: &str) -> bool {
+        for node in self.stack.drain(1..).rev() {
+            node.borrow_mut().blur();
+            self.log(Event::Popped(node.borrow().label()));
+        }
+
+        let labels: Vec<&str> = path.split('/').filter(|l| !l.is_empty()).collect();
+        for (i, label) in labels.iter().enumerate() {
+            let head = self.stack.last().unwrap().clone();
+            let entry = undecorated(&head)
+                .borrow()
+                .children()
+                .iter()
+                .find_map(|child| {
+                    let found = child.try_borrow().is_ok_and(|c| c.label() == *label);
+                    found.then(|| child.clone())
+                });
+            let Some(entry) = entry else {
+                return false;
+            };
+            if !entry.borrow().enabled() || watchdog::is_faulted(&entry) {
+                return false;
+            }
+
+            if !head.borrow_mut().select_entry(&entry) {
+                return false;
+            }
+            self.log(Event::Moved(String::from(*label)));
+
+            if entry.borrow().focusable() {
+                entry.borrow_mut().focus();
+                self.log(Event::Pushed(String::from(*label)));
+                self.stack.push(entry);
+            } else if i + 1 < labels.len() {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Pressing `combo` opens the overlay at `path` with [Overlay::navigate], e.g. ZL + Plus for
+    /// `"Savestates"`. In [Mode::Hold] the overlay stays open while the combo is held, in
+    /// [Mode::Toggle] pressing it again while open closes the overlay.
+    pub fn add_shortcut(&mut self, combo: impl Into<FlagSet<wut::gamepad::Button>>, path: &str) {
+        self.shortcuts.push((combo.into(), String::from(path)));
+    }
+
+    /// Removes the shortcuts to `path`.
+    pub fn remove_shortcut(&mut self, path: &str) {
+        self.shortcuts.retain(|(_, p)| p != path);
+    }
+
+    /// Passes every line shown on the HUD to `mirror` as well, e.g. [osscreen::tv_sink] or a
+    /// network stream for spectators, and an empty line when it is hidden. The mirror only
+    /// shows; input still comes from [Overlay::run]. Replaces the [Overlay::add_target] outputs.
+    pub fn set_mirror<F>(&mut self, mirror: Option<F>)
+    where
+        F: 'static + Fn(&str),
+    {
+        self.clear_targets();
+        if let Some(mirror) = mirror {
+            self.add_target(mirror, Detail::Line);
+        }
+    }
+
+    /// Shows the overlay on `sink` as well, with its own level of `detail`, e.g. a compact line
+    /// on the TV with [osscreen::sink_on] and the full list on the GamePad. Like the mirror, it
+    /// only shows and gets an empty line when the HUD is hidden.
+    pub fn add_target<F>(&mut self, sink: F, detail: Detail)
+    where
+        F: 'static + Fn(&str),
+    {
+        self.targets.push(Target {
+            sink: Box::new(sink),
+            detail,
+            shown: false,
+        });
+    }
+
+    /// Entries of the current menu shown at once by [Detail::List] outputs, scrolling with the
+    /// cursor. Defaults to 10.
+    pub fn set_list_window(&mut self, lines: usize) {
+        self.list_window = lines.max(1);
+    }
+
+    pub fn clear_targets(&mut self) {
+        self.hide_targets();
+        self.targets.clear();
+    }
+
+    fn hide_targets(&mut self) {
+        for target in self.targets.iter_mut().filter(|t| t.shown) {
+            (target.sink)("");
+            target.shown = false;
+        }
+    }
+
+    /// The navigation head in the list mode of [Menu::list], with up to
+    /// [Overlay::set_list_window] entries unless it is a list menu itself. Just `line` for heads
+    /// other than menus.
+    fn listing(&self, line: &str) -> String {
+        let head = undecorated(self.stack.last().unwrap());
+        let Ok(item) = head.try_borrow() else {
+            return String::from(line);
+        };
+
+        match (**item).as_any().downcast_ref::<Menu>() {
+            Some(menu) if self.open => {
+                let mut out = String::new();
+                menu.render_list(menu.window.unwrap_or(self.list_window), &mut out);
+                out
+            }
+            _ => String::from(line),
+        }
+    }
+
+    /// Number of entries on the navigation stack, the root included.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// See [find_path].
+    pub fn find_path(&self, path: &str) -> Option<Node> {
+        find_path(&self.root, path)
+    }
+
+    /// See [find].
+    pub fn find(&self, id: &str) -> Option<Node> {
+        find(&self.root, id)
+    }
+
+    /// Current state of the [Toggle] with the id `id`.
+    pub fn get_toggle(&self, id: &str) -> Option<bool> {
+        match self.find(id)?.try_borrow().ok()?.value()? {
+            Value::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Sets the [Toggle] with the id `id` and passes the state to its callback. Returns whether
+    /// the item was found and accepted the value.
+    pub fn set_toggle(&self, id: &str, value: bool) -> bool {
+        self.find(id)
+            .is_some_and(|node| watchdog::set_value(&node, &Value::Bool(value)))
+    }
+
+    /// Current value of the [Number] with the id `id`, converted to `T`.
+    pub fn get_number<T: NumericValue>(&self, id: &str) -> Option<T> {
+        T::from_value(&self.find(id)?.try_borrow().ok()?.value()?)
+    }
+
+    /// Sets the [Number] with the id `id`, clamped to its range, and applies it. Returns whether
+    /// the item was found and accepted the value.
+    pub fn set_number<T: NumericValue>(&self, id: &str, value: T) -> bool {
+        self.find(id)
+            .is_some_and(|node| watchdog::set_value(&node, &value.to_value()))
+    }
+
+    /// Indented dump of every item in the tree, entries on the navigation stack marked with `>`.
+    pub fn render_tree(&self) -> String {
+        fn walk(node: &Node, depth: usize, stack: &[Node], out: &mut String) {
+            let item = node.borrow();
+            let marker = if stack.iter().any(|n| Rc::ptr_eq(n, node)) {
+                '>'
+            } else {
+                ' '
+            };
+            let _ = writeln!(
+                out,
+                "{}{:indent$}{}",
+                marker,
+                "",
+                item.summary(),
+                indent = depth * 2 + 1
+            );
+            drop(item);
+            for child in undecorated(node).borrow().children() {
+                walk(child, depth + 1, stack, out);
+            }
+        }
+
+        let mut out = String::new();
+        walk(&self.root, 0, &self.stack, &mut out);
+        out
+    }
+
+    /// Rendered text of the current navigation head.
+    pub fn line(&self) -> String {
+        self.stack.last().unwrap().borrow().render()
+    }
+
+    /// Like [Overlay::line], written into `buf`. Returns the number of bytes written.
+    #[cfg(feature = "byte-render")]
+    pub fn line_bytes(&self, buf: &mut [u8]) -> usize {
+        self.stack.last().unwrap().borrow().render_bytes(buf)
+    }
+
+    pub fn render(&mut self) {
+        self.countdown = self.countdown();
+
+        let mut line = String::new();
+        let mut crumb = 0;
+        if let Some(breadcrumb) = self.breadcrumb.filter(|_| self.stack.len() > 1) {
+            line.push_str(&self.breadcrumb());
+            match breadcrumb {
+                Breadcrumb::Prefix => line.push_str(": "),
+                Breadcrumb::Line => {
+                    crumb = line.len();
+                    line.push('\n');
+                }
+            }
+        }
+        self.stack.last().unwrap().borrow().render_into(&mut line);
+        if let Some(seconds) = self.countdown {
+            let _ = write!(line, " ({})", seconds);
+        }
+        if self.flash > 0 {
+            let _ = write!(line, " ({})", self.flash_text);
+        }
+        self.overridden = self.override_state();
+        match self.overridden {
+            Some(Some(seconds)) => {
+                let _ = write!(line, " ({}s)", seconds);
+            }
+            Some(None) => line.push_str(" (override)"),
+            None => {}
+        }
+        if let Some(help) = self.hovered_help() {
+            line.push('\n');
+            line.push_str(&help);
+        }
+
+        self.write(&line, crumb);
+    }
+
+    /// Sets the items at the paths to the values for `ms` milliseconds, then sets their previous
+    /// values again. Both invoke the callbacks like a change by the user. While selected, an
+    /// overridden item shows the remaining seconds. Returns how many items accepted the values.
+    pub fn override_for(&mut self, values: &[(&str, Value)], ms: u32) -> usize {
+        let frames = (ms * FRAMES_PER_SECOND).div_ceil(1000);
+        self.apply_override(values, Until::Frames(frames))
+    }
+
+    /// Like [Overlay::override_for], but the previous values are set again once `done` returns
+    /// `true`. It is polled once per [Overlay::run].
+    pub fn override_until<F>(&mut self, values: &[(&str, Value)], done: F) -> usize
+    where
+        F: 'static + Fn() -> bool,
+    {
+        self.apply_override(values, Until::Condition(Box::new(done)))
+    }
+
+    fn apply_override(&mut self, values: &[(&str, Value)], until: Until) -> usize {
+        let mut items = vec![];
+
+        for (path, value) in values {
+            let Some(node) = find_path(&self.root, path) else {
+                continue;
+            };
+
+            // An item overridden again keeps the value from before the first override.
+            let earlier = self.overrides.iter_mut().find_map(|o| {
+                let i = o.items.iter().position(|(n, _)| Rc::ptr_eq(n, &node))?;
+                Some(o.items.remove(i).1)
+            });
+            let Some(original) = earlier.or_else(|| node.borrow().value()) else {
+                continue;
+            };
+
+            if watchdog::set_value(&node, value) {
+                items.push((node, original));
+            }
+        }
+
+        let applied = items.len();
+        self.overrides.retain(|o| !o.items.is_empty());
+        if applied > 0 {
+            self.overrides.push(Override { items, until });
+        }
+        applied
+    }
+
+    /// Ends the overrides that ran out. Returns whether any did.
+    fn expire_overrides(&mut self) -> bool {
+        let elapsed = self.elapsed;
+        let mut expired = false;
+
+        self.overrides.retain_mut(|o| {
+            let done = match &mut o.until {
+                Until::Frames(frames) => {
+                    *frames = frames.saturating_sub(elapsed);
+                    *frames == 0
+                }
+                Until::Condition(done) => done(),
+            };
+            if done {
+                for (node, value) in &o.items {
+                    watchdog::set_value(node, value);
+                }
+                expired = true;
+            }
+            !done
+        });
+
+        expired
+    }
+
+    /// Remaining seconds of the override of the selected item, `None` inside for one that ends
+    /// on a condition.
+    fn override_state(&self) -> Option<Option<u32>> {
+        let selected = self.selected()?;
+        let o = self
+            .overrides
+            .iter()
+            .find(|o| o.items.iter().any(|(n, _)| Rc::ptr_eq(n, &selected)))?;
+        Some(match o.until {
+            Until::Frames(frames) => Some(frames.div_ceil(FRAMES_PER_SECOND)),
+            Until::Condition(_) => None,
+        })
+    }
+
+    /// Passes `line` to the renderer and the targets. A breadcrumb on a line of its own ends at
+    /// `crumb`, and is put before the menu line for [Detail::Line] targets.
+    fn write(&mut self, line: &str, crumb: usize) {
+        if !self.targets.is_empty() {
+            let listing = self
+                .targets
+                .iter()
+                .any(|t| t.detail == Detail::List)
+                .then(|| self.listing(line));
+            let compact = (crumb > 0).then(|| {
+                let menu = line[crumb + 1..].lines().next().unwrap_or("");
+                format!("{}: {}", &line[..crumb], menu)
+            });
+            for target in &mut self.targets {
+                let text = match (target.detail, &listing, &compact) {
+                    (Detail::List, Some(listing), _) => listing.as_str(),
+                    (_, _, Some(compact)) => compact.as_str(),
+                    _ => line.lines().next().unwrap_or(""),
+                };
+                (target.sink)(text);
+                target.shown = !text.is_empty();
+            }
+        }
+
+        if self.shown {
+            let opacity = self.opacity.get();
+            if self.drawn_opacity != Some(opacity) {
+                self.renderer.set_opacity(opacity);
+                self.drawn_opacity = Some(opacity);
+            }
+            let listing = (self.renderer.detail() == Detail::List).then(|| self.listing(line));
+            let text = listing.as_deref().unwrap_or(line);
+            if let Err(e) = self.renderer.draw(text) {
+                self.set_error(Error::Text(e));
+            }
+        } else if self.failures >= SHOW_RETRY_LIMIT {
+            (self.fallback)(line);
+        }
+    }
+
+    /// Bar filling up while the combo is held but the hold delay has not passed yet.
+    fn hold_progress(&self) -> String {
+        const SEGMENTS: u32 = 8;
+        let filled = (self.combo_frames * SEGMENTS / self.hold_delay.max(1)).min(SEGMENTS);
+
+        let mut out = String::new();
+        bar(&mut out, filled, SEGMENTS);
+        out
+    }
+
+    /// Seconds until the overlay hides itself, once the end of the idle period is near.
+    fn countdown(&self) -> Option<u32> {
+        if self.mode != Mode::Toggle {
+            return None;
+        }
+
+        let remaining = self.auto_hide?.saturating_sub(self.idle);
+        (remaining <= COUNTDOWN_FRAMES).then(|| remaining.div_ceil(FRAMES_PER_SECOND))
+    }
+
+    /// Shows the HUD, see [render::Renderer::show].
+    ///
+    /// If the renderer cannot show it, e.g. the notification cannot be created, the attempt is
+    /// repeated on later calls with an exponential backoff. After repeated failures rendering goes
+    /// to the fallback sink until the renderer becomes available again.
+    pub fn show(&mut self) {
+        if self.shown {
+            return;
+        }
+
+        if self.retry_in > 0 {
+            self.retry_in -= 1;
+            return;
+        }
+
+        match self.renderer.show() {
+            Ok(()) => {
+                self.shown = true;
+                self.failures = 0;
+            }
+            Err(e) => {
+                self.failures = self.failures.saturating_add(1);
+                self.retry_in = (1 << self.failures.min(6)).min(SHOW_BACKOFF_MAX);
+                self.set_error(Error::Show(e));
+            }
+        }
+    }
+
+    /// Hides the HUD: the renderer draws an empty text.
+    pub fn hide(&mut self) {
+        if core::mem::take(&mut self.shown) {
+            let _ = self.renderer.draw("");
+        }
+        self.hide_targets();
+        self.retry_in = 0;
+    }
+
+    /// Closes the overlay until the combo is released and pressed again.
+    pub fn dismiss(&mut self) {
+        self.dismissed = true;
+        self.set_open(false);
+        self.hide();
+    }
+
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Whether the menu consumed the last frame of input. Hosts that hide input from the game
+    /// while the overlay is in use, e.g. in [Mode::Toggle], should do so while this is `true`.
+    ///
+    /// The frame that closes the overlay still counts, so the closing press does not reach the
+    /// game either.
+    pub fn captures_input(&self) -> bool {
+        self.open || self.capturing
+    }
+
+    /// Buttons of the last frame the menu used: all of them while [Overlay::captures_input], none
+    /// otherwise. Hosts hooking the game's input clear these before passing it on.
+    pub fn consumed(&self) -> FlagSet<wut::gamepad::Button> {
+        self.consumed
+    }
+
+    /// `input` without the [Overlay::consumed] buttons, and without the left stick while it
+    /// navigates the menu, see [Overlay::set_analog].
+    pub fn filter(&self, input: State) -> State {
+        let consumed = self.consumed;
+        State {
+            hold: input.hold - consumed,
+            trigger: input.trigger - consumed,
+            release: input.release - consumed,
+            left_stick: if self.analog && self.captures_input() {
+                None
+            } else {
+                input.left_stick
+            },
+            ..input
+        }
+    }
+
+    /// Frames the combo has to be held before it takes effect. Defaults to `0`.
+    pub fn set_hold_delay(&mut self, frames: u32) {
+        self.hold_delay = frames;
+    }
+
+    /// Opacity of the HUD backdrop in percent, see [render::Renderer::set_opacity].
+    pub fn opacity(&self) -> u8 {
+        self.opacity.get()
+    }
+
+    /// Lowers the opacity of the HUD backdrop so the game shows through while values are tuned
+    /// live. Defaults to `100`; renderers without a backdrop of their own ignore it.
+    pub fn set_opacity(&mut self, percent: u8) {
+        self.opacity.set(percent.min(100));
+    }
+
+    /// Submenu for the overlay's own settings: the theme, the HUD opacity and whether
+    /// [Overlay::set_autosave] is active, which only takes effect once it is set.
+    pub fn settings(&self, name: &str) -> Node {
+        let themes: Vec<(&str, &'static theme::Theme)> = vec![
+            ("Default", &theme::Theme::DEFAULT),
+            ("Plain", &theme::Theme::PLAIN),
+            ("Accessible", &theme::Theme::ACCESSIBLE),
+            ("Accessible wide", &theme::Theme::ACCESSIBLE_WIDE),
+        ];
+        let current = themes
+            .iter()
+            .position(|(_, theme)| *theme == theme::current())
+            .unwrap_or(0);
+        let opacity = self.opacity.clone();
+        let enabled = self.autosave_enabled.clone();
+
+        Menu::new(
+            name,
+            vec![
+                Select::with_index("Theme", themes, current, |_, selection| {
+                    theme::set(selection.value)
+                })
+                .into(),
+                Number::new("HUD opacity %", opacity.get(), 10, 0, 100, move |v| {
+                    opacity.set(*v)
+                }),
+                Toggle::new("Autosave", enabled.get(), move |v| enabled.set(v)),
+            ],
+        )
+    }
+
+    /// Measures hold, repeat and idle times with `clock`, a monotonic time in microseconds such as
+    /// `OSTicksToMicroseconds(OSGetTime())`, instead of counting calls to [Overlay::run].
+    pub fn set_clock<F>(&mut self, clock: F)
+    where
+        F: 'static + Fn() -> u64,
+    {
+        self.clock = Some(Box::new(clock));
+        self.time = None;
+    }
+
+    /// Disables items whose callbacks take longer than `us` microseconds to handle a frame of
+    /// input, see [watchdog]. Needs [Overlay::set_clock]; `None` turns the budget off.
+    pub fn set_callback_budget(&mut self, us: Option<u64>) {
+        self.callback_budget = us;
+    }
+
+    /// 60 Hz frames since the previous update according to the clock, `1` without one.
+    fn passed(&mut self) -> u32 {
+        const FRAME: u64 = 1_000_000 / FRAMES_PER_SECOND as u64;
+
+        let Some(clock) = &self.clock else {
+            return 1;
+        };
+
+        let now = clock();
+        let Some((last, carry)) = self.time else {
+            self.time = Some((now, 0));
+            return 1;
+        };
+
+        let passed = now.saturating_sub(last) + carry;
+        self.time = Some((now, passed % FRAME));
+        (passed / FRAME).min(u32::MAX as u64) as u32
+    }
+
+    /// Input timings of this overlay, see [timings].
+    pub fn timings(&self) -> timings::Timings {
+        self.timings
+    }
+
+    pub fn set_timings(&mut self, timings: timings::Timings) {
+        self.timings = timings;
+    }
+
+    /// Milliseconds the cursor rests on an item before its [MenuItem::help] is shown on a line
+    /// below the menu line, or `None` to never show help. Shown right away by default.
+    pub fn set_help_delay(&mut self, delay_ms: Option<u32>) {
+        self.help_delay = delay_ms.map(|ms| (ms * FRAMES_PER_SECOND).div_ceil(1000));
+    }
+
+    /// Help of the entry the cursor rests on, once the help delay has passed.
+    fn hovered_help(&self) -> Option<String> {
+        if self.hover.1 < self.help_delay? {
+            return None;
+        }
+        let current = self.stack.last()?.try_borrow().ok()?.current()?;
+        current.try_borrow().ok()?.help()
+    }
+
+    /// Follows the entry the cursor is on. Returns whether the help delay just passed.
+    fn hover(&mut self, elapsed: u32) -> bool {
+        let current = self
+            .stack
+            .last()
+            .and_then(|head| head.try_borrow().ok()?.current());
+        let same = match (&current, &self.hover.0) {
+            (Some(current), Some(hovered)) => hovered.ptr_eq(&Rc::downgrade(current)),
+            (None, None) => true,
+            _ => false,
+        };
+        if !same {
+            self.hover = (current.as_ref().map(Rc::downgrade), 0);
+            return false;
+        }
+
+        let Some(delay) = self.help_delay else {
+            return false;
+        };
+        let before = self.hover.1;
+        self.hover.1 = before.saturating_add(elapsed);
+        before < delay && self.hover.1 >= delay
+    }
+
+    /// Where the path to the current menu is shown, see [Overlay::breadcrumb]. Off by default.
+    pub fn set_breadcrumb(&mut self, breadcrumb: Option<Breadcrumb>) {
+        self.breadcrumb = breadcrumb;
+    }
+
+    /// Labels of the entries on the navigation stack joined by [theme::Theme::breadcrumb], e.g.
+    /// `Settings ▸ Video ▸ Gamma`.
+    pub fn breadcrumb(&self) -> String {
+        let labels: Vec<String> = self
+            .stack
+            .iter()
+            .filter_map(|node| node.try_borrow().ok().map(|item| item.label()))
+            .filter(|label| !label.is_empty())
+            .collect();
+        labels.join(theme::current().breadcrumb)
+    }
+
+    /// Whether a filling bar is shown while the combo is held for the hold delay. Enabled by
+    /// default.
+    pub fn set_hold_indicator(&mut self, enabled: bool) {
+        self.hold_indicator = enabled;
+    }
+
+    /// Whether the left stick navigates like the D-pad. Disabled by default.
+    pub fn set_analog(&mut self, enabled: bool) {
+        self.analog = enabled;
+    }
+
+    /// Stick mapping used for analog navigation, shared with e.g. a [StickCalibration].
+    pub fn calibration(&self) -> Rc<Cell<Calibration>> {
+        self.calibration.clone()
+    }
+
+    /// Combo that opens the [Palette] while the overlay is open.
+    pub fn set_palette_combo(&mut self, combo: Option<FlagSet<wut::gamepad::Button>>) {
+        self.palette_combo = combo;
+    }
+
+    /// Combos copying the value of the selected item and pasting it onto another one. Both are
+    /// disabled by default.
+    pub fn set_clipboard_combos(
+        &mut self,
+        copy: Option<FlagSet<wut::gamepad::Button>>,
+        paste: Option<FlagSet<wut::gamepad::Button>>,
+    ) {
+        self.copy_combo = copy;
+        self.paste_combo = paste;
+    }
+
+    /// The last copied value.
+    pub fn clipboard(&self) -> Option<&Value> {
+        self.clipboard.as_ref()
+    }
+
+    /// Whether a long press of X, see [timings::Timings::long_press], resets the selected item to
+    /// its default value. Disabled by default.
+    pub fn set_reset_hold(&mut self, enabled: bool) {
+        self.reset_hold = enabled;
+        self.reset_held = 0;
+    }
+
+    /// Resets the selected item once X has been held for a long press, returning whether it
+    /// changed.
+    fn reset(&mut self, input: &State) -> bool {
+        use wut::gamepad::Button as B;
+
+        if !self.reset_hold {
+            return false;
+        }
+        let frames = self.timings.long_press.max(1);
+
+        if !input.hold.contains(B::X) {
+            self.reset_held = 0;
+            return false;
+        }
+
+        let before = self.reset_held;
+        self.reset_held = self.reset_held.saturating_add(self.elapsed);
+        if !(before < frames && self.reset_held >= frames) {
+            return false;
+        }
+
+        let Some(item) = self.selected() else {
+            return false;
+        };
+        let Some(default) = item.borrow().default_value() else {
+            return false;
+        };
+
+        let reset = watchdog::set_value(&item, &default);
+        if reset {
+            self.flash("reset", FLASH_FRAMES);
+        }
+        reset
+    }
+
+    /// The entry the cursor of the innermost menu is on.
+    fn selected(&self) -> Option<Node> {
+        self.stack.last().unwrap().borrow().current()
+    }
+
+    fn copy(&mut self) {
+        if let Some(value) = self.selected().and_then(|item| item.borrow().value()) {
+            self.clipboard = Some(value);
         }
-        false
     }
-}
 
-// endregion
+    /// Applies the clipboard to the selected item. Items reject values of other kinds.
+    fn paste(&mut self) -> bool {
+        match (self.selected(), &self.clipboard) {
+            (Some(item), Some(value)) => watchdog::set_value(&item, value),
+            _ => false,
+        }
+    }
 
-// region: Text
+    /// Pushes a [Palette] listing every action in the tree onto the navigation stack.
+    pub fn open_palette(&mut self) {
+        let mut palette = Palette::empty();
+        palette.collect(&self.root);
+        self.stack.push(Rc::new(RefCell::new(Box::new(palette))));
+    }
 
-pub struct Text {
-    f: Box<dyn Fn() -> String>,
-}
+    /// Frames without input after which an overlay in [Mode::Toggle] closes itself.
+    pub fn set_auto_hide(&mut self, frames: Option<u32>) {
+        self.auto_hide = frames;
+    }
 
-impl Text {
-    pub fn new<F>(f: F) -> Node
+    /// Whether pressing B at the root menu closes the overlay. Disabled by default.
+    pub fn set_close_on_back(&mut self, enabled: bool) {
+        self.close_on_back = enabled;
+    }
+
+    /// Replaces the sink used while the renderer cannot show the HUD. Defaults to the log, or
+    /// to [osscreen::sink] with the `osscreen` feature.
+    pub fn set_fallback<F>(&mut self, f: F)
     where
-        F: 'static + Fn() -> String,
+        F: 'static + Fn(&str),
     {
-        Rc::new(RefCell::new(Box::new(Self { f: Box::new(f) })))
+        self.fallback = Box::new(f);
     }
-}
 
-impl MenuItem for Text {
-    fn render(&self) -> String {
-        format!("{}", (self.f)())
+    /// Most recent error, if any.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
     }
 
-    fn control(&mut self, _input: State, _stack: &mut Vec<Node>) -> bool {
-        true
+    /// Returns and clears the most recent error.
+    pub fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
     }
-}
 
-// endregion
+    /// Configures an overlay for `root` before creating it.
+    pub fn builder(root: Node) -> OverlayBuilder {
+        OverlayBuilder {
+            root,
+            combo: wut::gamepad::Button::L | wut::gamepad::Button::R,
+            mode: Mode::Hold,
+            hold_delay: 0,
+            restore: None,
+            save_prompt: false,
+            safe_mode: None,
+        }
+    }
 
-// region: Number
+    /// Combo used by [Overlay::poll]. Defaults to L + R.
+    pub fn set_combo(&mut self, combo: impl Into<FlagSet<wut::gamepad::Button>>) {
+        self.combo = combo.into();
+    }
 
-pub struct Number<T: Display + core::ops::AddAssign + core::ops::SubAssign + PartialOrd + Clone> {
-    text: String,
-    value: T,
-    inc: T,
-    min: T,
-    max: T,
-    f: Box<dyn Fn(&T)>,
-}
+    /// Like [Overlay::run] with the combo set by [Overlay::set_combo].
+    pub fn poll(&mut self, input: State) {
+        let update = self.step(input, self.combo);
+        self.update(update);
+    }
 
-impl<T: 'static + Display + core::ops::AddAssign + core::ops::SubAssign + PartialOrd + Clone>
-    Number<T>
-{
-    pub fn new<F>(text: &str, value: T, inc: T, min: T, max: T, f: F) -> Node
-    where
-        F: 'static + Fn(&T),
-    {
-        Rc::new(RefCell::new(Box::new(Self {
-            text: String::from(text),
-            value,
-            inc,
-            min,
-            max,
-            f: Box::new(f),
-        })))
+    pub fn run(&mut self, input: State, combo: impl Into<FlagSet<wut::gamepad::Button>>) {
+        let update = self.step(input, combo.into());
+        self.update(update);
     }
-}
 
-impl<T: Display + core::ops::AddAssign + core::ops::SubAssign + PartialOrd + Clone> MenuItem
-    for Number<T>
-{
-    fn render(&self) -> String {
-        let icon = if self.value == self.min {
-            icons::ARROW_UP
-        } else if self.value == self.max {
-            icons::ARROW_DOWN
-        } else {
-            icons::ARROW_UP_DOWN
-        };
+    /// Like [Overlay::run] for several frames of input at once, processed in order. The HUD is
+    /// only updated once, for the state after the last input.
+    pub fn run_batch(&mut self, inputs: &[State], combo: impl Into<FlagSet<wut::gamepad::Button>>) {
+        let combo = combo.into();
+        let mut dirty = false;
+        let mut last = None;
+
+        for input in inputs {
+            let update = self.step(*input, combo);
+            if let Update::Render(changed) = update {
+                dirty |= changed;
+            }
+            last = Some(update);
+        }
 
-        format!("{}: {} {}", self.text, self.value, icon)
+        match last {
+            Some(Update::Render(_)) => self.update(Update::Render(dirty)),
+            Some(update) => self.update(update),
+            None => {}
+        }
     }
 
-    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
-        use wut::gamepad::Button as B;
-        let mut changed = false;
-        if input.trigger.contains(B::Up) {
-            let mut new = self.value.clone();
-            new += self.inc.clone();
+    /// Advances one frame of input and returns what the HUD should show afterwards.
+    fn step(&mut self, input: State, combo: FlagSet<wut::gamepad::Button>) -> Update {
+        self.frame += 1;
+        let elapsed = self.passed();
+        self.elapsed = elapsed;
 
-            if new <= self.max {
-                self.value = new;
-            } else {
-                self.value = self.max.clone();
+        #[cfg(feature = "notifications")]
+        self.toasts.retain_mut(|(_, frames)| {
+            *frames = frames.saturating_sub(elapsed);
+            *frames > 0
+        });
+        let mut ctx = MenuContext::new(self.root.clone(), elapsed, self.timings);
+        let ticked = self.root.borrow_mut().tick_ctx(&mut ctx);
+        let ticked = self.carry_out(ctx.requests) || ticked;
+        let ticked = self.expire_overrides() || ticked;
+        let ticked = self.show_errors() || ticked;
+        if self.safe_mode.as_ref().is_some_and(|safe| safe.load.get()) {
+            self.load_full_menu();
+        }
+        if let Some(autosave) = self.autosave.as_mut().filter(|_| self.safe_mode.is_none()) {
+            autosave.elapsed = autosave.elapsed.saturating_add(elapsed);
+            if autosave.elapsed >= autosave.interval {
+                let saved = autosave.save(&self.root, self.autosave_enabled.get());
+                self.report_save(saved);
             }
-            changed = true;
         }
+        let held = input.hold.contains(combo);
+        let required = self.hold_delay.max(1);
+        let was_open = self.open;
+        self.capturing = was_open;
+        let was_held = self.combo_frames;
+        let prompted = self.prompt.is_some();
 
-        if input.trigger.contains(B::Down) {
-            let mut new = self.value.clone();
-            new -= self.inc.clone();
+        if held {
+            self.combo_frames = self.combo_frames.saturating_add(elapsed);
+        } else {
+            self.combo_frames = 0;
+            self.dismissed = false;
+        }
 
-            if new >= self.min && new < self.value {
-                self.value = new;
+        let shortcut = self
+            .shortcuts
+            .iter()
+            .find(|(combo, _)| input.hold.contains(*combo) && !(input.trigger & *combo).is_empty())
+            .cloned();
+        if let Some((combo, path)) = shortcut {
+            if self.mode == Mode::Toggle && self.open {
+                self.close(false);
             } else {
-                self.value = self.min.clone();
+                self.navigate(&path);
+                self.set_open(true);
+                self.shortcut = Some(combo);
+                self.idle = 0;
             }
-            changed = true;
         }
 
-        if input.trigger.contains(B::A) {
-            (self.f)(&self.value);
+        match self.mode {
+            Mode::Hold => {
+                self.shortcut = self.shortcut.filter(|combo| input.hold.contains(*combo));
+                if (self.combo_frames >= required && !self.dismissed) || self.shortcut.is_some() {
+                    self.set_open(true);
+                } else if self.open {
+                    self.close(false);
+                }
+            }
+            Mode::Toggle => {
+                if was_held < required && self.combo_frames >= required {
+                    if self.open {
+                        self.close(false);
+                    } else {
+                        self.set_open(true);
+                    }
+                    self.idle = 0;
+                }
+            }
         }
 
-        changed
-    }
-}
-
-// endregion
+        self.consumed = if self.open || was_open {
+            input.hold | input.trigger | input.release
+        } else {
+            FlagSet::default()
+        };
 
-// region: Select
+        if !self.open {
+            return if held && self.combo_frames < required && self.hold_indicator && !self.dismissed
+            {
+                Update::Indicator
+            } else {
+                Update::Hide
+            };
+        }
 
-pub struct Selection<T> {
-    pub name: String,
-    pub value: T,
-}
+        if self.mode == Mode::Toggle {
+            if input.hold.is_empty() && input.trigger.is_empty() {
+                self.idle = self.idle.saturating_add(elapsed);
+            } else {
+                self.idle = 0;
+            }
 
-impl<T> From<(&str, T)> for Selection<T> {
-    fn from(value: (&str, T)) -> Self {
-        Self {
-            name: String::from(value.0),
-            value: value.1,
+            if self.auto_hide.is_some_and(|frames| self.idle >= frames) {
+                self.close(false);
+                if !self.open {
+                    return Update::Hide;
+                }
+            }
         }
-    }
-}
 
-impl From<&str> for Selection<String> {
-    fn from(value: &str) -> Self {
-        Self {
-            name: String::from(value),
-            value: String::from(value),
+        let pressed = |combo: Option<FlagSet<wut::gamepad::Button>>| {
+            combo.is_some_and(|combo| {
+                input.hold.contains(combo) && !(input.trigger & combo).is_empty()
+            })
+        };
+
+        // A release of X ending a reset hold is not a press of its own.
+        let resetting = self.reset_hold && self.reset_held >= self.timings.long_press.max(1);
+        let input = if resetting {
+            State {
+                release: input.release - wut::gamepad::Button::X,
+                ..input
+            }
+        } else {
+            input
+        };
+
+        let flash = self.flash;
+        let changed = if pressed(self.palette_combo) {
+            self.open_palette();
+            true
+        } else if pressed(self.copy_combo) {
+            self.copy();
+            false
+        } else if pressed(self.paste_combo) {
+            self.paste()
+        } else {
+            self.control(input)
+        };
+        let changed = changed | self.reset(&input);
+
+        self.answer_prompt();
+        if !self.open {
+            return Update::Hide;
         }
+
+        let flashed = self.flash > flash || (self.flash > 0 && self.flash <= elapsed);
+        self.flash = self.flash.saturating_sub(elapsed);
+        let hovered = self.hover(elapsed);
+
+        Update::Render(
+            changed
+                || ticked
+                || hovered
+                || flashed
+                || !was_open
+                || prompted != self.prompt.is_some()
+                || self.override_state() != self.overridden
+                || self.countdown() != self.countdown,
+        )
     }
-}
 
-impl<T: Display> From<T> for Selection<T> {
-    fn from(value: T) -> Self {
-        Self {
-            name: format!("{}", value),
-            value,
+    fn update(&mut self, update: Update) {
+        match update {
+            Update::Hide => self.hide(),
+            Update::Indicator => {
+                self.show();
+                let bar = self.hold_progress();
+                self.write(&bar, 0);
+            }
+            Update::Render(dirty) => {
+                let hidden = !self.shown;
+                self.show();
+                if dirty || (hidden && self.shown) {
+                    self.render();
+                }
+            }
         }
     }
 }
 
-pub struct Select<T> {
-    text: String,
-    options: Vec<Selection<T>>,
-    index: usize,
-    f: Box<dyn Fn(usize, &Selection<T>)>,
+unsafe impl Send for Overlay {}
+
+/// Where [Overlay::set_breadcrumb] shows the path to the current menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breadcrumb {
+    /// Before the menu line, e.g. `Settings ▸ Video: < Gamma: 1.20 >`.
+    Prefix,
+    /// On a line of its own above the menu line.
+    Line,
 }
 
-impl<T: 'static> Select<T> {
-    pub fn new<F>(text: &str, options: Vec<impl Into<Selection<T>>>, f: F) -> Node
-    where
-        F: 'static + Fn(usize, &Selection<T>),
-    {
-        Rc::new(RefCell::new(Box::new(Self {
-            text: String::from(text),
-            options: options.into_iter().map(Into::into).collect(),
-            index: 0,
-            f: Box::new(f),
-        })))
-    }
+/// How much of the overlay an [Overlay::add_target] output shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detail {
+    /// The HUD line, of which [Overlay::add_target] outputs only show the first line, with a
+    /// [Breadcrumb::Line] put in front of it.
+    Line,
+    /// While the overlay is open, the current menu in the list mode of [Menu::list].
+    List,
 }
 
-impl<T> MenuItem for Select<T> {
-    fn render(&self) -> String {
-        let icon = if self.index == 0 {
-            icons::ARROW_UP
-        } else if self.index == self.options.len() - 1 {
-            icons::ARROW_DOWN
-        } else {
-            icons::ARROW_UP_DOWN
-        };
+struct Target {
+    sink: Box<dyn Fn(&str)>,
+    detail: Detail,
+    /// Whether the last text passed to the sink was not empty.
+    shown: bool,
+}
 
-        format!("{}: {} {}", self.text, self.options[self.index].name, icon)
-    }
+/// Menu replaced in safe mode, see [OverlayBuilder::safe_mode].
+struct SafeMode {
+    root: Node,
+    /// Set by the "Load full menu" button.
+    load: Rc<Cell<bool>>,
+    restore: Option<(Rc<dyn persist::Storage>, String)>,
+    /// Whether to set up the save prompt for `restore`, see [OverlayBuilder::save_prompt].
+    save_prompt: bool,
+}
 
-    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
-        use wut::gamepad::Button as B;
-        let mut changed = false;
-        if input.trigger.contains(B::Up) {
-            if self.index < self.options.len() - 1 {
-                self.index += 1
-            };
-            changed = true;
-        }
+/// Target and schedule of [Overlay::set_autosave].
+struct Autosave {
+    storage: Rc<dyn persist::Storage>,
+    key: String,
+    interval: u32,
+    elapsed: u32,
+    /// Values last written, to skip saves without changes.
+    saved: persist::StateBlob,
+}
 
-        if input.trigger.contains(B::Down) {
-            if self.index > 0 {
-                self.index -= 1;
-            }
-            changed = true;
+impl Autosave {
+    /// Writes the values of the items below `root` if `enabled` and they changed since the last
+    /// save. A failed write is retried on the next one.
+    fn save(&mut self, root: &Node, enabled: bool) -> Result<(), String> {
+        self.elapsed = 0;
+        if !enabled {
+            return Ok(());
         }
 
-        if input.trigger.contains(B::A) {
-            (self.f)(self.index, &self.options[self.index]);
+        let values = persist::StateBlob::capture(root);
+        if values.differs(&self.saved) {
+            self.storage.save(&self.key, &values.to_bytes())?;
+            self.saved = values;
         }
-
-        changed
+        Ok(())
     }
 }
 
-// endregion
+/// Values set by [Overlay::override_for] or [Overlay::override_until], with the ones to restore.
+struct Override {
+    items: Vec<(Node, Value)>,
+    until: Until,
+}
 
-// region: Toggle
+enum Until {
+    Frames(u32),
+    Condition(Box<dyn Fn() -> bool>),
+}
 
-pub struct Toggle {
-    text: String,
-    value: bool,
-    f: Box<dyn Fn(bool)>,
+/// Answers to the [ClosePrompt].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Closing {
+    Save,
+    Discard,
+    Cancel,
 }
 
-impl Toggle {
-    pub fn new<F>(text: &str, value: bool, f: F) -> Node
-    where
-        F: 'static + Fn(bool),
-    {
-        Rc::new(RefCell::new(Box::new(Self {
-            text: String::from(text),
-            value,
-            f: Box::new(f),
-        })))
-    }
+/// "Save changes?" shown by [Overlay::set_save_prompt]. Left/Right picks an answer, A confirms
+/// and B cancels.
+struct ClosePrompt {
+    choice: Closing,
+    answer: Rc<Cell<Option<Closing>>>,
 }
 
-impl MenuItem for Toggle {
+impl MenuItem for ClosePrompt {
     fn render(&self) -> String {
-        format!("{} [{}]", self.text, if self.value { "X" } else { "  " })
+        let mut out = String::from("Save changes?");
+        for (choice, name) in [
+            (Closing::Save, "Save"),
+            (Closing::Discard, "Discard"),
+            (Closing::Cancel, "Cancel"),
+        ] {
+            let _ = if choice == self.choice {
+                write!(out, " [{}]", name)
+            } else {
+                write!(out, " {}", name)
+            };
+        }
+        out
     }
 
-    fn control(&mut self, input: State, _stack: &mut Vec<Node>) -> bool {
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
         use wut::gamepad::Button as B;
-        let mut changed = false;
+        let t = input.trigger;
 
-        if input.trigger.contains(B::A) {
-            self.value = !self.value;
-            (self.f)(self.value);
-            changed = true;
+        if t.contains(B::A) || t.contains(B::B) {
+            if t.contains(B::B) {
+                self.choice = Closing::Cancel;
+            }
+            self.answer.set(Some(self.choice));
+            stack.pop();
+        } else if t.contains(B::Left) {
+            self.choice = match self.choice {
+                Closing::Save => Closing::Cancel,
+                Closing::Discard => Closing::Save,
+                Closing::Cancel => Closing::Discard,
+            };
+        } else if t.contains(B::Right) {
+            self.choice = match self.choice {
+                Closing::Save => Closing::Discard,
+                Closing::Discard => Closing::Cancel,
+                Closing::Cancel => Closing::Save,
+            };
+        } else {
+            return false;
         }
 
-        changed
+        true
     }
 }
 
-// endregion
+/// [Overlay] that passes every rendered line to a sink instead of showing a notification, for
+/// hosts drawing the menu in their own UI. Does not need the `notifications` feature.
+pub struct RawOverlay(Overlay);
 
-// region: Root
+impl RawOverlay {
+    pub fn new<F>(root: Node, sink: F) -> Self
+    where
+        F: 'static + Fn(&str),
+    {
+        Self(Overlay::with_renderer(root, Box::new(sink)))
+    }
 
-pub struct Overlay {
-    hud: Option<notifications::Notification>,
+    /// Like [RawOverlay::new], drawing with `renderer`.
+    pub fn with_renderer<R>(root: Node, renderer: R) -> Self
+    where
+        R: 'static + render::Renderer,
+    {
+        Self(Overlay::with_renderer(root, Box::new(renderer)))
+    }
+
+    pub fn into_inner(self) -> Overlay {
+        self.0
+    }
+}
+
+/// Settings applied when creating an [Overlay], see [Overlay::builder].
+pub struct OverlayBuilder {
     root: Node,
-    stack: Vec<Node>,
+    combo: FlagSet<wut::gamepad::Button>,
+    mode: Mode,
+    hold_delay: u32,
+    restore: Option<(Rc<dyn persist::Storage>, String)>,
+    save_prompt: bool,
+    safe_mode: Option<(Rc<dyn persist::Storage>, String)>,
 }
 
-impl Overlay {
-    pub fn new(root: Node) -> Self {
-        let mut r = Self {
-            hud: None,
-            root,
-            stack: vec![],
-        };
+impl OverlayBuilder {
+    /// Buttons opening and closing the overlay with [Overlay::poll]. Defaults to L + R.
+    pub fn combo(mut self, combo: impl Into<FlagSet<wut::gamepad::Button>>) -> Self {
+        self.combo = combo.into();
+        self
+    }
 
-        r.stack.push(r.root.clone());
-        r.root.borrow_mut().focus();
+    /// Whether the combo is held or pressed to toggle, see [Mode]. Defaults to [Mode::Hold].
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
 
-        r
+    /// See [Overlay::set_hold_delay].
+    pub fn hold_delay(mut self, frames: u32) -> Self {
+        self.hold_delay = frames;
+        self
     }
 
-    pub fn control(&mut self, input: State) -> bool {
-        self.stack
-            .last()
-            .unwrap()
-            .clone()
-            .borrow_mut()
-            .control(input, &mut self.stack)
+    /// Values saved under `key`, e.g. with [persist::save] on a [persist::Sd], applied to the
+    /// items when the overlay is created.
+    pub fn restore(mut self, storage: Rc<dyn persist::Storage>, key: &str) -> Self {
+        self.restore = Some((storage, String::from(key)));
+        self
     }
 
-    pub fn render(&self) {
-        if let Some(hud) = &self.hud {
-            let head = self.stack.last().unwrap().clone();
-            let _ = hud.text(&head.borrow().render());
-        }
+    /// Asks whether to save changed values when the overlay is closed, see
+    /// [Overlay::set_save_prompt], writing to the target of [OverlayBuilder::restore]. Without
+    /// one there is nothing to save to and no prompt.
+    pub fn save_prompt(mut self) -> Self {
+        self.save_prompt = true;
+        self
     }
 
-    pub fn show(&mut self) {
-        if self.hud.is_none() {
-            self.hud = Some(notifications::dynamic("").show().unwrap());
-            // self.render();
-        }
+    /// Marks the session as running under `key` in `storage`, e.g. a [persist::Sd], until
+    /// [Overlay::end_session] or the overlay is dropped. If the mark of the previous session is
+    /// still there, it crashed and the overlay starts in safe mode: the [theme::Theme::DEFAULT]
+    /// theme, a banner and a "Load full menu" button in place of the root, and no values restored
+    /// or autosaved until the full menu is loaded. Lazy menus below the root are thereby not
+    /// entered either.
+    pub fn safe_mode(mut self, storage: Rc<dyn persist::Storage>, key: &str) -> Self {
+        self.safe_mode = Some((storage, String::from(key)));
+        self
     }
 
-    pub fn hide(&mut self) {
-        self.hud = None;
+    /// Overlay shown as a dynamic notification.
+    #[cfg(feature = "notifications")]
+    pub fn build(self) -> Overlay {
+        self.apply(Overlay::new(self.root.clone()))
     }
 
-    pub fn run(&mut self, input: State, combo: impl Into<FlagSet<wut::gamepad::Button>>) {
-        if input.hold.contains(combo.into()) {
-            self.show();
+    /// Overlay writing its line to `sink`, see [RawOverlay].
+    pub fn build_raw<F>(self, sink: F) -> RawOverlay
+    where
+        F: 'static + Fn(&str),
+    {
+        RawOverlay(self.apply(Overlay::with_renderer(self.root.clone(), Box::new(sink))))
+    }
+
+    /// Overlay drawn by `renderer`, see [RawOverlay::with_renderer].
+    pub fn build_with<R>(self, renderer: R) -> RawOverlay
+    where
+        R: 'static + render::Renderer,
+    {
+        RawOverlay(self.apply(Overlay::with_renderer(
+            self.root.clone(),
+            Box::new(renderer),
+        )))
+    }
+
+    fn apply(&self, mut overlay: Overlay) -> Overlay {
+        overlay.set_combo(self.combo);
+        overlay.set_mode(self.mode);
+        overlay.set_hold_delay(self.hold_delay);
 
-            if self.control(input) {
-                self.render();
+        if let Some((storage, key)) = &self.safe_mode {
+            let crashed = storage.load(key).is_some_and(|mark| !mark.is_empty());
+            let _ = storage.save(key, b"running");
+            overlay.sentinel = Some((storage.clone(), key.clone()));
+            if crashed {
+                theme::set(&theme::Theme::DEFAULT);
+                overlay.enter_safe_mode(self.restore.clone(), self.save_prompt);
+                return overlay;
+            }
+        }
+
+        if let Some((storage, key)) = &self.restore {
+            persist::restore(&overlay.root, &**storage, key);
+            if self.save_prompt {
+                overlay.set_save_prompt(Some((storage.clone(), key)));
             }
-        } else {
-            self.hide();
         }
+        overlay
     }
 }
 
-unsafe impl Send for Overlay {}
+impl Drop for Overlay {
+    /// See [Overlay::end_session].
+    fn drop(&mut self) {
+        self.end_session();
+    }
+}
+
+impl core::ops::Deref for RawOverlay {
+    type Target = Overlay;
+
+    fn deref(&self) -> &Overlay {
+        &self.0
+    }
+}
+
+impl core::ops::DerefMut for RawOverlay {
+    fn deref_mut(&mut self) -> &mut Overlay {
+        &mut self.0
+    }
+}
 
 // endregion
@@ -0,0 +1,268 @@
+//! Emergency renderer writing the menu line straight into the OSScreen framebuffers, or the whole
+//! current menu with a [Window].
+//!
+//! Meant for recovery and diagnostics when the notifications module is unavailable, e.g. very
+//! early at boot or when another module conflicts with it. Drawing takes over the screens drawn on,
+//! both by default, so the game's own output there is hidden while a line is shown.
+//!
+//! The system font only covers ASCII; other characters are drawn as `?`, so
+//! [crate::theme::Theme::PLAIN] is the theme to use with this renderer.
+
+use crate::{Detail, render::Renderer};
+use core::{
+    cell::{Cell, RefCell},
+    ffi::c_void,
+    fmt::Write,
+};
+use wut::{
+    alloc::alloc::{Layout, alloc_zeroed, dealloc},
+    bindings as c,
+    prelude::*,
+};
+
+/// Screens drawn on by a [Screen].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screens {
+    Tv,
+    GamePad,
+    Both,
+}
+
+impl Screens {
+    fn ids(self) -> &'static [c::OSScreenID] {
+        match self {
+            Screens::Tv => &[c::SCREEN_TV],
+            Screens::GamePad => &[c::SCREEN_DRC],
+            Screens::Both => &[c::SCREEN_TV, c::SCREEN_DRC],
+        }
+    }
+}
+
+/// Alignment OSScreen requires of framebuffers.
+const BUFFER_ALIGN: usize = 0x100;
+
+/// Whole screen framebuffers for the TV and/or the GamePad. Output is disabled and the buffers
+/// are freed when it is dropped.
+pub struct Screen {
+    buffers: Vec<(c::OSScreenID, *mut u8, u32)>,
+    /// Whether output is enabled, i.e. the screens show the buffers instead of the game.
+    shown: Cell<bool>,
+    /// RGBA colour the buffers are cleared with before drawing.
+    backdrop: Cell<u32>,
+}
+
+impl Screen {
+    /// Initializes OSScreen and allocates the framebuffers of both screens.
+    pub fn new() -> Option<Self> {
+        Self::on(Screens::Both)
+    }
+
+    /// Like [Screen::new] for the TV only, leaving the GamePad to the game.
+    pub fn tv() -> Option<Self> {
+        Self::on(Screens::Tv)
+    }
+
+    /// Like [Screen::new] for `screens` only, leaving the others to the game.
+    pub fn on(screens: Screens) -> Option<Self> {
+        let screens = screens.ids();
+        // Dropped on failure, which frees the buffers allocated so far.
+        let mut this = Self {
+            buffers: Vec::with_capacity(screens.len()),
+            shown: Cell::new(false),
+            backdrop: Cell::new(0x0000_00ff),
+        };
+
+        unsafe {
+            c::OSScreenInit();
+
+            for &screen in screens {
+                let size = c::OSScreenGetBufferSizeEx(screen);
+                let layout = Layout::from_size_align(size as usize, BUFFER_ALIGN).ok()?;
+                let ptr = alloc_zeroed(layout);
+                if ptr.is_null() {
+                    return None;
+                }
+
+                c::OSScreenSetBufferEx(screen, ptr as *mut c_void);
+                this.buffers.push((screen, ptr, size));
+            }
+        }
+
+        Some(this)
+    }
+
+    /// Opacity of the black backdrop behind the text in percent, opaque by default. Takes effect
+    /// with the next text drawn.
+    pub fn set_opacity(&self, percent: u8) {
+        let alpha = u32::from(percent.min(100)) * 0xff / 100;
+        self.backdrop.set(alpha);
+    }
+
+    /// Draws the lines of `text` from the top left of the screens, enabling output if needed. An
+    /// empty text [hides](Screen::hide) them instead.
+    pub fn draw(&self, text: &str) {
+        if text.is_empty() {
+            self.hide();
+            return;
+        }
+
+        for &(screen, ptr, size) in &self.buffers {
+            unsafe {
+                c::OSScreenClearBufferEx(screen, self.backdrop.get());
+
+                for (row, line) in text.lines().enumerate() {
+                    let mut bytes: Vec<u8> = line
+                        .chars()
+                        .map(|c| if c.is_ascii() { c as u8 } else { b'?' })
+                        .collect();
+                    bytes.push(0);
+                    c::OSScreenPutFontEx(screen, 0, row as u32, bytes.as_ptr() as *const _);
+                }
+
+                c::DCFlushRange(ptr as *mut c_void, size);
+                c::OSScreenFlipBuffersEx(screen);
+                if !self.shown.get() {
+                    c::OSScreenEnableEx(screen, true);
+                }
+            }
+        }
+        self.shown.set(true);
+    }
+
+    /// Clears the buffers and disables output, so the screens show the game again.
+    pub fn hide(&self) {
+        if !self.shown.replace(false) {
+            return;
+        }
+
+        for &(screen, ptr, size) in &self.buffers {
+            unsafe {
+                c::OSScreenClearBufferEx(screen, 0);
+                c::DCFlushRange(ptr as *mut c_void, size);
+                c::OSScreenFlipBuffersEx(screen);
+                c::OSScreenEnableEx(screen, false);
+            }
+        }
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        self.hide();
+
+        for &(_, ptr, size) in &self.buffers {
+            // The layout was valid when the buffer was allocated.
+            unsafe {
+                dealloc(
+                    ptr,
+                    Layout::from_size_align_unchecked(size as usize, BUFFER_ALIGN),
+                )
+            };
+        }
+    }
+}
+
+/// Sink for [crate::RawOverlay::new] or [crate::Overlay::set_fallback] drawing with a [Screen],
+/// which is set up on the first line shown. If OSScreen cannot be set up, lines go to the log.
+pub fn sink() -> impl Fn(&str) {
+    let screen: RefCell<Option<Option<Screen>>> = RefCell::new(None);
+
+    move |line| {
+        if line.is_empty() && screen.borrow().is_none() {
+            return;
+        }
+        match screen.borrow_mut().get_or_insert_with(Screen::new) {
+            Some(screen) => screen.draw(line),
+            None if line.is_empty() => {}
+            None => wut::println!("[overlay] {}", line),
+        }
+    }
+}
+
+/// Sink for [crate::Overlay::set_mirror] drawing on the TV only, see [sink_on]. Like every sink
+/// here, it gives the screens back to the game whenever the line is empty.
+pub fn tv_sink() -> impl Fn(&str) {
+    sink_on(Screens::Tv)
+}
+
+/// Sink for [crate::Overlay::add_target] drawing on `screens` only, set up like [sink]. If
+/// OSScreen cannot be set up, lines are dropped.
+pub fn sink_on(screens: Screens) -> impl Fn(&str) {
+    let screen: RefCell<Option<Option<Screen>>> = RefCell::new(None);
+
+    move |line| {
+        if line.is_empty() && screen.borrow().is_none() {
+            return;
+        }
+        if let Some(screen) = screen
+            .borrow_mut()
+            .get_or_insert_with(|| Screen::on(screens))
+        {
+            screen.draw(line);
+        }
+    }
+}
+
+/// Full screen menu for [crate::RawOverlay::with_renderer]: the current menu with all its
+/// entries, one per line, in an ASCII border. OSScreen is set up on the first lines drawn; if
+/// that fails, nothing is drawn. The screens are given back to the game while no lines are drawn
+/// and once the window is dropped.
+pub struct Window {
+    screens: Screens,
+    screen: Option<Option<Screen>>,
+    /// Backdrop opacity for the screen once it is set up.
+    opacity: u8,
+}
+
+impl Window {
+    pub fn new(screens: Screens) -> Self {
+        Self {
+            screens,
+            screen: None,
+            opacity: 100,
+        }
+    }
+}
+
+impl Renderer for Window {
+    fn draw(&mut self, text: &str) -> Result<(), String> {
+        if text.is_empty() {
+            if let Some(Some(screen)) = &self.screen {
+                screen.hide();
+            }
+            return Ok(());
+        }
+
+        let (screens, opacity) = (self.screens, self.opacity);
+        let Some(screen) = self.screen.get_or_insert_with(|| {
+            let screen = Screen::on(screens)?;
+            screen.set_opacity(opacity);
+            Some(screen)
+        }) else {
+            return Ok(());
+        };
+
+        let width = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let border = format!("+{}+", "-".repeat(width + 2));
+
+        let mut out = border.clone();
+        for line in text.lines() {
+            let _ = write!(out, "\n| {:1$} |", line, width);
+        }
+        out.push('\n');
+        out.push_str(&border);
+        screen.draw(&out);
+        Ok(())
+    }
+
+    fn detail(&self) -> Detail {
+        Detail::List
+    }
+
+    fn set_opacity(&mut self, percent: u8) {
+        self.opacity = percent;
+        if let Some(Some(screen)) = &self.screen {
+            screen.set_opacity(percent);
+        }
+    }
+}
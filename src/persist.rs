@@ -0,0 +1,231 @@
+//! Key-value storage for widget state that should survive restarts.
+//!
+//! The overlay only touches the file system through [Sd]; otherwise the host decides where the
+//! bytes go.
+
+use crate::{Node, Value, watchdog};
+use core::{cell::RefCell, fmt::Write};
+use wut::{alloc::collections::BTreeMap, prelude::*};
+
+pub trait Storage {
+    /// Bytes last saved under `key`, if any.
+    fn load(&self, key: &str) -> Option<Vec<u8>>;
+
+    /// Stores `data` under `key`, or describes why it could not.
+    fn save(&self, key: &str, data: &[u8]) -> Result<(), String>;
+}
+
+/// Storage that only lives as long as the process, e.g. for the simulator or as a default.
+#[derive(Default)]
+pub struct Memory {
+    entries: RefCell<BTreeMap<String, Vec<u8>>>,
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for Memory {
+    fn load(&self, key: &str) -> Option<Vec<u8>> {
+        self.entries.borrow().get(key).cloned()
+    }
+
+    fn save(&self, key: &str, data: &[u8]) -> Result<(), String> {
+        self.entries
+            .borrow_mut()
+            .insert(String::from(key), data.to_vec());
+        Ok(())
+    }
+}
+
+/// Root of the SD card.
+pub const SD_ROOT: &str = "fs:/vol/external01";
+
+/// Storage keeping every key as a file in a directory on the SD card, e.g.
+/// `fs:/vol/external01/overlay/<key>`.
+pub struct Sd {
+    dir: String,
+}
+
+impl Sd {
+    /// Storage in `dir` below [SD_ROOT], which is created if missing.
+    pub fn new(dir: &str) -> Self {
+        let dir = format!("{}/{}", SD_ROOT, dir.trim_matches('/'));
+        let _ = wut::fs::create_dir_all(&dir);
+        Self { dir }
+    }
+
+    fn path(&self, key: &str) -> String {
+        format!("{}/{}", self.dir, key)
+    }
+
+    fn temp(&self, key: &str) -> String {
+        format!("{}/{}.tmp", self.dir, key)
+    }
+}
+
+impl Storage for Sd {
+    /// Falls back to the temporary file of [Sd::save] if a crash came between removing the old
+    /// file and renaming the new one.
+    fn load(&self, key: &str) -> Option<Vec<u8>> {
+        wut::fs::read(self.path(key))
+            .or_else(|_| wut::fs::read(self.temp(key)))
+            .ok()
+    }
+
+    /// Writes to a temporary file first and renames it in place of the old one, so a crash while
+    /// saving leaves the previous data intact. The old file is removed before the rename, as the
+    /// Wii U file system does not rename onto existing files.
+    fn save(&self, key: &str, data: &[u8]) -> Result<(), String> {
+        let path = self.path(key);
+        let temp = self.temp(key);
+        wut::fs::write(&temp, data).map_err(|e| format!("writing {}: {:?}", temp, e))?;
+        // Fails if there is no old file; a failure that matters shows up in the rename.
+        let _ = wut::fs::remove_file(&path);
+        wut::fs::rename(&temp, &path).map_err(|e| format!("renaming {}: {:?}", temp, e))
+    }
+}
+
+/// Encodes `(key, value)` pairs like those of [crate::values], one per line.
+pub fn encode(values: &[(String, Value)]) -> Vec<u8> {
+    let mut out = String::new();
+    for (path, value) in values {
+        let _ = match value {
+            Value::Bool(v) => writeln!(out, "{}\tb:{}", path, *v as u8),
+            Value::Int(v) => writeln!(out, "{}\ti:{}", path, v),
+            Value::Float(v) => writeln!(out, "{}\tf:{}", path, v),
+            Value::Index(v) => writeln!(out, "{}\tx:{}", path, v),
+            Value::Text(v) => writeln!(out, "{}\tt:{}", path, v.replace('\n', " ")),
+        };
+    }
+    out.into_bytes()
+}
+
+/// Values of a menu tree at one point in time, in the format of [encode].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StateBlob {
+    values: Vec<(String, Value)>,
+}
+
+impl StateBlob {
+    /// Current values of the items below `root`, keyed by `#` and the [crate::MenuItem::id] for
+    /// items that have one and by their path like [crate::values] otherwise.
+    pub fn capture(root: &Node) -> Self {
+        Self {
+            values: crate::keyed_values(root),
+        }
+    }
+
+    /// Reverse of [StateBlob::to_bytes]. Malformed lines are skipped.
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Self {
+            values: decode(data),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode(&self.values)
+    }
+
+    pub fn values(&self) -> &[(String, Value)] {
+        &self.values
+    }
+
+    /// Value stored for the item with `key`, see [StateBlob::capture].
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Sets the stored values on the items below `root`, skipping those already holding them.
+    /// Returns how many items hold their stored value afterwards.
+    pub fn apply(&self, root: &Node) -> usize {
+        self.values
+            .iter()
+            .filter(|(key, value)| {
+                lookup(root, key).is_some_and(|node| {
+                    node.try_borrow()
+                        .is_ok_and(|item| item.value().as_ref() == Some(value))
+                        || watchdog::set_value(&node, value)
+                })
+            })
+            .count()
+    }
+
+    /// Keys whose value differs from `other`, with the value here and the one in `other`.
+    /// Keys missing on one side have `None` there. Ordered like this blob, followed by the keys
+    /// only `other` has.
+    pub fn diff(&self, other: &StateBlob) -> Vec<(String, Option<Value>, Option<Value>)> {
+        let ours: BTreeMap<&str, &Value> = self.values.iter().map(|(k, v)| (&**k, v)).collect();
+        let theirs: BTreeMap<&str, &Value> = other.values.iter().map(|(k, v)| (&**k, v)).collect();
+
+        let mut out: Vec<_> = self
+            .values
+            .iter()
+            .filter_map(|(key, old)| {
+                let new = theirs.get(&**key).copied();
+                (new != Some(old)).then(|| (key.clone(), Some(old.clone()), new.cloned()))
+            })
+            .collect();
+
+        out.extend(
+            other
+                .values
+                .iter()
+                .filter(|(key, _)| !ours.contains_key(&**key))
+                .map(|(key, new)| (key.clone(), None, Some(new.clone()))),
+        );
+        out
+    }
+
+    /// Whether any value differs from `other`, see [StateBlob::diff].
+    pub fn differs(&self, other: &StateBlob) -> bool {
+        !self.diff(other).is_empty()
+    }
+}
+
+/// Reverse of [encode]. Malformed lines are skipped.
+pub fn decode(data: &[u8]) -> Vec<(String, Value)> {
+    let Ok(text) = core::str::from_utf8(data) else {
+        return vec![];
+    };
+
+    text.lines()
+        .filter_map(|line| {
+            let (path, value) = line.split_once('\t')?;
+            let (kind, value) = value.split_once(':')?;
+            let value = match kind {
+                "b" => Value::Bool(value == "1"),
+                "i" => Value::Int(value.parse().ok()?),
+                "f" => Value::Float(value.parse().ok()?),
+                "x" => Value::Index(value.parse().ok()?),
+                "t" => Value::Text(String::from(value)),
+                _ => return None,
+            };
+            Some((String::from(path), value))
+        })
+        .collect()
+}
+
+/// The item below `root` a key of [StateBlob::capture] stands for: the one with the id after a
+/// `#`, or else the one at the path.
+fn lookup(root: &Node, key: &str) -> Option<Node> {
+    match key.strip_prefix('#') {
+        Some(id) => crate::find(root, id),
+        None => crate::find_path(root, key),
+    }
+}
+
+/// Applies the values saved under `key`, e.g. by [crate::Overlay::set_save_prompt], to the items
+/// below `root` with [StateBlob::apply]. Returns how many were accepted.
+pub fn restore(root: &Node, storage: &dyn Storage, key: &str) -> usize {
+    storage
+        .load(key)
+        .map_or(0, |data| StateBlob::from_bytes(&data).apply(root))
+}
+
+/// Writes the values of the items below `root` to `storage` under `key`, see [restore].
+pub fn save(root: &Node, storage: &dyn Storage, key: &str) -> Result<(), String> {
+    storage.save(key, &StateBlob::capture(root).to_bytes())
+}
@@ -0,0 +1,58 @@
+//! Ready-made practice submenu: savestate slots, a position store/recall pair, a frame counter and
+//! an RNG seed display, all backed by host callbacks.
+
+use crate::{Button, Menu, Node, Select, Text, Unit, UnitValue};
+use core::cell::Cell;
+use wut::{
+    alloc::{boxed::Box, rc::Rc},
+    prelude::*,
+};
+
+/// Host callbacks used by [menu]. Savestate callbacks receive the selected slot, starting at 0.
+pub struct Hooks {
+    pub save_state: Box<dyn Fn(usize)>,
+    pub load_state: Box<dyn Fn(usize)>,
+    pub store_position: Box<dyn Fn()>,
+    pub recall_position: Box<dyn Fn()>,
+    /// Current frame count of the game.
+    pub frame: Box<dyn Fn() -> u64>,
+    /// Current RNG state of the game.
+    pub seed: Box<dyn Fn() -> u64>,
+}
+
+/// Builds the practice submenu with `slots` savestate slots.
+pub fn menu(name: &str, slots: usize, hooks: Hooks) -> Node {
+    let hooks = Rc::new(hooks);
+    let slot = Rc::new(Cell::new(0usize));
+
+    Menu::new(
+        name,
+        vec![
+            Select::new("Slot", (1..=slots.max(1)).collect::<Vec<_>>(), {
+                let slot = slot.clone();
+                move |index, _| slot.set(index)
+            }),
+            Button::new("Save state", {
+                let (hooks, slot) = (hooks.clone(), slot.clone());
+                move || (hooks.save_state)(slot.get())
+            }),
+            Button::new("Load state", {
+                let (hooks, slot) = (hooks.clone(), slot.clone());
+                move || (hooks.load_state)(slot.get())
+            }),
+            Button::new("Store position", {
+                let hooks = hooks.clone();
+                move || (hooks.store_position)()
+            }),
+            Button::new("Recall position", {
+                let hooks = hooks.clone();
+                move || (hooks.recall_position)()
+            }),
+            UnitValue::new("Frame", &Unit::FRAMES, {
+                let hooks = hooks.clone();
+                move || (hooks.frame)() as f64
+            }),
+            Text::new(move || format!("Seed: {:016X}", (hooks.seed)())),
+        ],
+    )
+}
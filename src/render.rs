@@ -0,0 +1,84 @@
+//! Backends drawing the lines of an [crate::Overlay].
+//!
+//! Any `Fn(&str)` is a [Renderer], so simple sinks need no type of their own. Backends with more
+//! to draw, e.g. a framebuffer or a remote viewer, implement the trait directly.
+
+use crate::Detail;
+use wut::prelude::*;
+
+/// Draws the overlay, one entry per line.
+pub trait Renderer {
+    /// Gets ready to draw, e.g. by creating a notification. The overlay retries failures with a
+    /// backoff, drawing to its fallback sink meanwhile.
+    fn show(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Shows `text`, one entry per line, in place of what was drawn before, or hides it if it is
+    /// empty.
+    fn draw(&mut self, text: &str) -> Result<(), String>;
+
+    /// How much of the overlay to draw. Backends with room for the whole menu, e.g. a full
+    /// screen, ask for [Detail::List].
+    fn detail(&self) -> Detail {
+        Detail::Line
+    }
+
+    /// Sets the opacity of the backdrop drawn behind the text in percent, see
+    /// [crate::Overlay::set_opacity]. Called before the next text is drawn whenever it changed.
+    /// Backends without a backdrop of their own ignore it.
+    fn set_opacity(&mut self, percent: u8) {
+        let _ = percent;
+    }
+}
+
+impl<F: Fn(&str)> Renderer for F {
+    fn draw(&mut self, text: &str) -> Result<(), String> {
+        self(text);
+        Ok(())
+    }
+}
+
+/// Dynamic notification, created by [Renderer::show] or the first text drawn and removed again
+/// when the text is empty. The renderer of [crate::Overlay::new].
+///
+/// The notifications module draws its backdrop itself, so [Renderer::set_opacity] is ignored.
+#[cfg(feature = "notifications")]
+#[derive(Default)]
+pub struct Notification {
+    hud: Option<notifications::Notification>,
+}
+
+#[cfg(feature = "notifications")]
+impl Notification {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "notifications")]
+impl Renderer for Notification {
+    fn show(&mut self) -> Result<(), String> {
+        if self.hud.is_none() {
+            let hud = notifications::dynamic("").show();
+            self.hud = Some(hud.map_err(|e| format!("{:?}", e))?);
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, text: &str) -> Result<(), String> {
+        if text.is_empty() {
+            self.hud = None;
+            return Ok(());
+        }
+
+        match &self.hud {
+            Some(hud) => hud.text(text).map_err(|e| format!("{:?}", e)),
+            None => {
+                let hud = notifications::dynamic(text).show();
+                self.hud = Some(hud.map_err(|e| format!("{:?}", e))?);
+                Ok(())
+            }
+        }
+    }
+}
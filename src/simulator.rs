@@ -0,0 +1,400 @@
+//! Host-side simulator for developing menus on a PC.
+//!
+//! Every line read from stdin is one button press: it lists the buttons pressed together
+//! (`a`, `b`, `x`, `y`, `l`, `r`, `zl`, `zr`, `up`, `down`, `left`, `right`, `plus`, `minus`),
+//! which are held for one frame and released on the next. `q` quits.
+//!
+//! [Harness] drives the same logic from scripted input for use in `#[test]`s, and
+//! [assert_snapshot] compares its [Harness::snapshot] against a file on disk.
+
+use crate::{
+    Node, Overlay,
+    theme::{self, Theme},
+};
+use core::fmt::Write as _;
+use std::{
+    boxed::Box,
+    env, fs,
+    io::{self, BufRead, Write},
+    path::Path,
+    string::String,
+    sync::{Mutex, MutexGuard, PoisonError},
+    vec::Vec,
+};
+use wut::{
+    flagset::FlagSet,
+    gamepad::{Button, State},
+};
+
+// region: MockInput
+
+/// Produces gamepad states from the set of held buttons, deriving `trigger` and `release` from
+/// the previous frame like the console does.
+#[derive(Default)]
+pub struct MockInput {
+    hold: FlagSet<Button>,
+}
+
+impl MockInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn next(&mut self, hold: impl Into<FlagSet<Button>>) -> State {
+        let hold = hold.into();
+        let state = State {
+            hold,
+            trigger: hold - self.hold,
+            release: self.hold - hold,
+            ..Default::default()
+        };
+        self.hold = hold;
+        state
+    }
+}
+
+pub fn parse_button(name: &str) -> Option<Button> {
+    use Button as B;
+    Some(match name.to_ascii_lowercase().as_str() {
+        "a" => B::A,
+        "b" => B::B,
+        "x" => B::X,
+        "y" => B::Y,
+        "l" => B::L,
+        "r" => B::R,
+        "zl" => B::ZL,
+        "zr" => B::ZR,
+        "up" | "u" => B::Up,
+        "down" | "d" => B::Down,
+        "left" | "<" => B::Left,
+        "right" | ">" => B::Right,
+        "plus" | "+" => B::Plus,
+        "minus" | "-" => B::Minus,
+        _ => return None,
+    })
+}
+
+// endregion
+
+// region: Terminal
+
+/// Renders the overlay line to stdout.
+#[derive(Default)]
+pub struct Terminal;
+
+impl Terminal {
+    pub fn draw(&mut self, line: &str) {
+        let mut out = io::stdout().lock();
+        let _ = writeln!(out, "| {}", line);
+        let _ = out.flush();
+    }
+}
+
+// endregion
+
+// region: Simulator
+
+pub struct Simulator {
+    overlay: Overlay,
+    input: MockInput,
+    terminal: Terminal,
+}
+
+impl Simulator {
+    pub fn new(root: Node) -> Self {
+        Self {
+            overlay: Overlay::with_renderer(root, Box::new(|_: &str| {})),
+            input: MockInput::new(),
+            terminal: Terminal,
+        }
+    }
+
+    pub fn overlay(&mut self) -> &mut Overlay {
+        &mut self.overlay
+    }
+
+    /// Advances one frame with `hold` held. Returns whether the line changed.
+    pub fn step(&mut self, hold: impl Into<FlagSet<Button>>) -> bool {
+        let state = self.input.next(hold);
+        let changed = self.overlay.control(state);
+        if changed {
+            self.terminal.draw(&self.overlay.line());
+        }
+        changed
+    }
+
+    /// Reads presses from stdin until it is closed or `q` is entered.
+    pub fn run(&mut self) -> io::Result<()> {
+        self.terminal.draw(&self.overlay.line());
+
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            let mut hold = FlagSet::<Button>::default();
+
+            for token in line.split_whitespace() {
+                if token == "q" {
+                    return Ok(());
+                }
+                match parse_button(token) {
+                    Some(button) => hold |= button,
+                    None => std::eprintln!("unknown button: {}", token),
+                }
+            }
+
+            self.step(hold);
+            self.step(FlagSet::<Button>::default());
+        }
+
+        Ok(())
+    }
+}
+
+// endregion
+
+// region: Harness
+
+/// Change of the navigation stack caused by a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackOp {
+    None,
+    Push,
+    Pop(usize),
+}
+
+/// Outcome of a single scripted frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub changed: bool,
+    pub line: String,
+    pub depth: usize,
+    pub op: StackOp,
+    /// See [Overlay::render_tree].
+    pub tree: String,
+}
+
+/// Held by the living [Harness], as the theme it renders with is global. Tests running in
+/// parallel thereby take turns.
+static THEME: Mutex<()> = Mutex::new(());
+
+/// Feeds scripted input into an [Overlay] and records what happens after every frame.
+///
+/// Only one harness exists at a time: creating one waits until the previous one is dropped,
+/// which sets the theme from before it again.
+///
+/// ```ignore
+/// let mut h = Harness::new(menu);
+/// h.press(Button::Right).press(Button::A);
+/// assert_eq!(h.depth(), 2);
+/// assert_eq!(h.frames().last().unwrap().op, StackOp::None);
+/// ```
+pub struct Harness {
+    overlay: Overlay,
+    input: MockInput,
+    frames: Vec<Frame>,
+    theme: &'static Theme,
+    /// Theme set before the harness was created.
+    previous: &'static Theme,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Harness {
+    /// Creates a harness rendering with [Theme::PLAIN].
+    pub fn new(root: Node) -> Self {
+        // A test failing while holding the lock leaves nothing inconsistent behind.
+        let lock = THEME.lock().unwrap_or_else(PoisonError::into_inner);
+        let previous = theme::current();
+        theme::set(&Theme::PLAIN);
+        Self {
+            overlay: Overlay::with_renderer(root, Box::new(|_: &str| {})),
+            input: MockInput::new(),
+            frames: Vec::new(),
+            theme: &Theme::PLAIN,
+            previous,
+            _lock: lock,
+        }
+    }
+
+    pub fn with_theme(mut self, theme: &'static Theme) -> Self {
+        self.theme = theme;
+        theme::set(theme);
+        self
+    }
+
+    pub fn overlay(&mut self) -> &mut Overlay {
+        &mut self.overlay
+    }
+
+    /// Feeds a raw state.
+    pub fn feed(&mut self, state: State) -> &Frame {
+        theme::set(self.theme);
+
+        let before = self.overlay.depth();
+        let changed = self.overlay.control(state);
+        self.record(before, changed)
+    }
+
+    /// Like [Harness::frame], but through [Overlay::run], so ticks, bindings and the reset hold
+    /// run like on the console. The combo is empty and thereby always held, which keeps the
+    /// overlay open.
+    pub fn run(&mut self, hold: impl Into<FlagSet<Button>>) -> &mut Self {
+        theme::set(self.theme);
+
+        let state = self.input.next(hold);
+        let before = self.overlay.depth();
+        let line = self.overlay.line();
+        self.overlay.run(state, FlagSet::<Button>::default());
+        let changed = self.overlay.line() != line;
+        self.record(before, changed);
+        self
+    }
+
+    fn record(&mut self, before: usize, changed: bool) -> &Frame {
+        let depth = self.overlay.depth();
+
+        let op = if depth > before {
+            StackOp::Push
+        } else if depth < before {
+            StackOp::Pop(before - depth)
+        } else {
+            StackOp::None
+        };
+
+        self.frames.push(Frame {
+            changed,
+            line: self.overlay.line(),
+            depth,
+            op,
+            tree: self.overlay.render_tree(),
+        });
+        self.frames.last().unwrap()
+    }
+
+    /// Feeds one frame with `hold` held.
+    pub fn frame(&mut self, hold: impl Into<FlagSet<Button>>) -> &mut Self {
+        let state = self.input.next(hold);
+        self.feed(state);
+        self
+    }
+
+    /// Holds `buttons` for `frames` frames, then releases them.
+    pub fn hold(&mut self, buttons: impl Into<FlagSet<Button>>, frames: usize) -> &mut Self {
+        let buttons = buttons.into();
+        for _ in 0..frames {
+            self.frame(buttons);
+        }
+        self.frame(FlagSet::<Button>::default())
+    }
+
+    /// Presses and releases `buttons`.
+    pub fn press(&mut self, buttons: impl Into<FlagSet<Button>>) -> &mut Self {
+        self.hold(buttons, 1)
+    }
+
+    /// Feeds every state of `script` in order.
+    pub fn play(&mut self, script: impl IntoIterator<Item = State>) -> &mut Self {
+        for state in script {
+            self.feed(state);
+        }
+        self
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Rendered lines of all frames that reported a change.
+    pub fn renders(&self) -> Vec<&str> {
+        self.frames
+            .iter()
+            .filter(|f| f.changed)
+            .map(|f| f.line.as_str())
+            .collect()
+    }
+
+    pub fn line(&self) -> String {
+        self.overlay.line()
+    }
+
+    /// Deterministic dump of every recorded frame.
+    pub fn snapshot(&self) -> String {
+        let mut out = String::new();
+        for (i, frame) in self.frames.iter().enumerate() {
+            let op = match frame.op {
+                StackOp::None => String::new(),
+                StackOp::Push => String::from(" push"),
+                StackOp::Pop(n) => std::format!(" pop {}", n),
+            };
+            let _ = writeln!(out, "#{} depth {}{}", i, frame.depth, op);
+            let _ = writeln!(out, "> {}", frame.line);
+            out.push_str(&frame.tree);
+        }
+        out
+    }
+
+    pub fn depth(&self) -> usize {
+        self.overlay.depth()
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        theme::set(self.previous);
+    }
+}
+
+// endregion
+
+// region: Snapshot
+
+/// Line-based difference between two snapshots, `None` if they are equal.
+pub fn diff(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut out = String::new();
+
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) if e == a => {
+                let _ = writeln!(out, "  {}", e);
+            }
+            (e, a) => {
+                if let Some(e) = e {
+                    let _ = writeln!(out, "- {}", e);
+                }
+                if let Some(a) = a {
+                    let _ = writeln!(out, "+ {}", a);
+                }
+            }
+        }
+    }
+
+    Some(out)
+}
+
+/// Compares `actual` with the snapshot stored at `path`.
+///
+/// The file is (re)written instead if it does not exist yet or `UPDATE_SNAPSHOTS` is set.
+///
+/// Panics with a diff if the snapshot does not match.
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).unwrap();
+        }
+        fs::write(path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(path).unwrap();
+    if let Some(diff) = diff(&expected, actual) {
+        std::panic!("snapshot {} does not match:\n{}", path.display(), diff);
+    }
+}
+
+// endregion
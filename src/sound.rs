@@ -0,0 +1,237 @@
+//! Audio feedback for navigating, confirming and errors.
+//!
+//! The tones come from the active [crate::theme::Theme], so themes can be silent or use their own
+//! cues, unless an item brings its own with [crate::Cues]. Playback goes through a player
+//! installed with [set_player], e.g. [ax] playing on one voice of the AX mixer; without one, or
+//! while disabled, nothing is played. Like the theme, the settings are global and take effect on
+//! the next cue.
+
+use core::{
+    ffi::c_void,
+    ptr,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+use wut::{
+    alloc::alloc::{Layout, alloc, dealloc},
+    bindings as c,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cue {
+    /// The cursor moved to another item.
+    Navigate,
+    /// A value changed, an action ran or a submenu was entered.
+    Confirm,
+    /// A callback reported an error.
+    Error,
+}
+
+/// Short sine tone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tone {
+    /// Pitch in Hz.
+    pub frequency: u16,
+    pub duration_ms: u16,
+    /// Loudness from 0 to 100.
+    pub volume: u8,
+}
+
+/// Tone for each [Cue]; `None` plays nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sounds {
+    pub navigate: Option<Tone>,
+    pub confirm: Option<Tone>,
+    pub error: Option<Tone>,
+}
+
+impl Sounds {
+    pub const DEFAULT: Sounds = Sounds {
+        navigate: Some(Tone {
+            frequency: 880,
+            duration_ms: 20,
+            volume: 30,
+        }),
+        confirm: Some(Tone {
+            frequency: 1320,
+            duration_ms: 40,
+            volume: 40,
+        }),
+        error: Some(Tone {
+            frequency: 220,
+            duration_ms: 150,
+            volume: 50,
+        }),
+    };
+
+    pub const SILENT: Sounds = Sounds {
+        navigate: None,
+        confirm: None,
+        error: None,
+    };
+
+    pub fn tone(&self, cue: Cue) -> Option<Tone> {
+        match cue {
+            Cue::Navigate => self.navigate,
+            Cue::Confirm => self.confirm,
+            Cue::Error => self.error,
+        }
+    }
+}
+
+/// The installed `fn(Tone)`, or null.
+static PLAYER: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Plays the tone of `cue` from the active theme.
+pub fn play(cue: Cue) {
+    play_from(cue, None);
+}
+
+/// Plays the tone of `cue` from `sounds`, or from the active theme if `None`.
+pub fn play_from(cue: Cue, sounds: Option<Sounds>) {
+    if !enabled() {
+        return;
+    }
+
+    let player = PLAYER.load(Ordering::Relaxed);
+    if player.is_null() {
+        return;
+    }
+    // SAFETY: only `set_player` stores into `PLAYER`, and it stores `fn(Tone)`s.
+    let player: fn(Tone) = unsafe { core::mem::transmute(player) };
+
+    let sounds = sounds.unwrap_or(crate::theme::current().sounds);
+    if let Some(tone) = sounds.tone(cue) {
+        player(tone);
+    }
+}
+
+/// Function playing a [Tone], e.g. [ax]. `None` removes the player.
+pub fn set_player(player: Option<fn(Tone)>) {
+    let player = player.map_or(ptr::null_mut(), |player| player as *mut ());
+    PLAYER.store(player, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Priority of the voice acquired by [ax], below the game's usual voices.
+const AX_PRIORITY: u32 = 1;
+
+/// Voice acquired by [ax], or null.
+static VOICE: AtomicPtr<c::AXVoice> = AtomicPtr::new(ptr::null_mut());
+/// Samples of the tone last played by [ax], or null, and their number.
+static SAMPLES: AtomicPtr<i16> = AtomicPtr::new(ptr::null_mut());
+static SAMPLES_LEN: AtomicUsize = AtomicUsize::new(0);
+
+/// Player for [set_player] playing tones as 16 bit PCM on one voice of the AX mixer, on the TV
+/// and the GamePad. AX must have been initialized by the host; the voice is acquired on the first
+/// tone and kept. A tone cuts off the one still playing.
+pub fn ax(tone: Tone) {
+    unsafe {
+        let mut voice = VOICE.load(Ordering::Relaxed);
+        if voice.is_null() {
+            voice = c::AXAcquireVoice(AX_PRIORITY, None, ptr::null_mut());
+            if voice.is_null() {
+                return;
+            }
+            VOICE.store(voice, Ordering::Relaxed);
+        }
+
+        c::AXVoiceBegin(voice);
+        c::AXSetVoiceState(voice, c::AX_VOICE_STATE_STOPPED);
+        free_samples();
+
+        let rate = c::AXGetInputSamplesPerSec();
+        let len = (rate as usize * tone.duration_ms as usize / 1000).max(1);
+        let Some(samples) = sine(tone.frequency as u32, rate, len) else {
+            c::AXVoiceEnd(voice);
+            return;
+        };
+        SAMPLES.store(samples, Ordering::Relaxed);
+        SAMPLES_LEN.store(len, Ordering::Relaxed);
+
+        let mut offsets: c::AXVoiceOffsets = core::mem::zeroed();
+        offsets.dataType = c::AX_VOICE_FORMAT_LPCM16 as _;
+        offsets.loopingEnabled = c::AX_VOICE_LOOP_DISABLED as _;
+        offsets.endOffset = len as u32 - 1;
+        offsets.data = samples as *const c_void;
+        c::AXSetVoiceOffsets(voice, &mut offsets);
+        c::AXSetVoiceSrcType(voice, c::AX_VOICE_SRC_TYPE_NONE);
+        c::AXSetVoiceSrcRatio(voice, 1.0);
+
+        let volume = (tone.volume.min(100) as u32 * 0x8000 / 100) as u16;
+        let mut ve: c::AXVoiceVeData = core::mem::zeroed();
+        ve.volume = volume;
+        c::AXSetVoiceVe(voice, &mut ve);
+
+        let mut tv: [c::AXVoiceDeviceMixData; 6] = core::mem::zeroed();
+        let mut drc: [c::AXVoiceDeviceMixData; 4] = core::mem::zeroed();
+        for channel in [&mut tv[0], &mut tv[1], &mut drc[0], &mut drc[1]] {
+            channel.bus[0].volume = volume;
+        }
+        c::AXSetVoiceDeviceMix(voice, c::AX_DEVICE_TYPE_TV, 0, tv.as_mut_ptr());
+        c::AXSetVoiceDeviceMix(voice, c::AX_DEVICE_TYPE_DRC, 0, drc.as_mut_ptr());
+
+        c::AXSetVoiceState(voice, c::AX_VOICE_STATE_PLAYING);
+        c::AXVoiceEnd(voice);
+    }
+}
+
+/// Stops [ax] and releases its voice and samples, e.g. before the host shuts AX down.
+pub fn ax_release() {
+    let voice = VOICE.swap(ptr::null_mut(), Ordering::Relaxed);
+    if voice.is_null() {
+        return;
+    }
+
+    unsafe {
+        c::AXVoiceBegin(voice);
+        c::AXSetVoiceState(voice, c::AX_VOICE_STATE_STOPPED);
+        c::AXVoiceEnd(voice);
+        c::AXFreeVoice(voice);
+        free_samples();
+    }
+}
+
+fn samples_layout(len: usize) -> Option<Layout> {
+    Layout::from_size_align(len * size_of::<i16>(), 0x40).ok()
+}
+
+/// Frees the samples of the last tone. The voice must not be playing them anymore.
+unsafe fn free_samples() {
+    let samples = SAMPLES.swap(ptr::null_mut(), Ordering::Relaxed);
+    let len = SAMPLES_LEN.swap(0, Ordering::Relaxed);
+    if let (false, Some(layout)) = (samples.is_null(), samples_layout(len)) {
+        unsafe { dealloc(samples as *mut u8, layout) };
+    }
+}
+
+/// `len` samples of a sine at `frequency` Hz sampled at `rate` Hz, flushed for the DSP.
+unsafe fn sine(frequency: u32, rate: u32, len: usize) -> Option<*mut i16> {
+    let layout = samples_layout(len)?;
+    let samples = unsafe { alloc(layout) } as *mut i16;
+    if samples.is_null() {
+        return None;
+    }
+
+    for i in 0..len {
+        // Bhaskara's approximation of the sine, on degrees.
+        let degrees = (i as u64 * frequency as u64 * 360 / rate.max(1) as u64 % 360) as i64;
+        let (d, sign) = if degrees < 180 {
+            (degrees, 1)
+        } else {
+            (degrees - 180, -1)
+        };
+        let sample = sign * 4 * d * (180 - d) * i16::MAX as i64 / (40500 - d * (180 - d));
+        unsafe { samples.add(i).write(sample as i16) };
+    }
+
+    unsafe { c::DCFlushRange(samples as *mut c_void, layout.size() as u32) };
+    Some(samples)
+}
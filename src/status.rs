@@ -0,0 +1,153 @@
+//! Packs always-on status widgets, e.g. FPS, a timer and the position, into a fixed number of
+//! lines.
+//!
+//! Widgets are placed by priority, highest first, each on the first line it fits on. What does
+//! not fit anymore is handled by the [Overflow] rule, so the HUD never grows beyond its lines.
+
+use wut::{alloc::boxed::Box, prelude::*};
+
+/// Separates widgets sharing a line.
+const SEPARATOR: &str = " | ";
+
+/// What happens to a widget that does not fit on any line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// The widget is left out.
+    Drop,
+    /// The widget is cut to the room left on the line with the most of it and marked with `~`,
+    /// or left out if fewer than [Overflow::MIN_TRUNCATED] characters would remain.
+    Truncate,
+}
+
+impl Overflow {
+    /// Fewest characters a truncated widget keeps, the marker included.
+    pub const MIN_TRUNCATED: usize = 4;
+}
+
+struct Status {
+    name: String,
+    priority: u8,
+    f: Box<dyn Fn() -> String>,
+}
+
+pub struct Layout {
+    lines: usize,
+    width: usize,
+    overflow: Overflow,
+    widgets: Vec<Status>,
+    sink: Box<dyn Fn(&str)>,
+    shown: String,
+}
+
+impl Layout {
+    /// Layout of `lines` lines of up to `width` characters, passing the text to `sink` whenever
+    /// it changes.
+    pub fn new<F>(lines: usize, width: usize, sink: F) -> Self
+    where
+        F: 'static + Fn(&str),
+    {
+        Self {
+            lines: lines.max(1),
+            width,
+            overflow: Overflow::Drop,
+            widgets: vec![],
+            sink: Box::new(sink),
+            shown: String::new(),
+        }
+    }
+
+    /// Like [Layout::new], shown in a [crate::render::Notification] of its own.
+    #[cfg(feature = "notifications")]
+    pub fn notification(lines: usize, width: usize) -> Self {
+        use crate::render::Renderer;
+
+        let hud = core::cell::RefCell::new(crate::render::Notification::new());
+        Self::new(lines, width, move |text| {
+            let _ = hud.borrow_mut().draw(text);
+        })
+    }
+
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    /// Adds a widget showing the text returned by `f`. Higher priorities are placed first; equal
+    /// ones in the order they were added. An empty text takes no room.
+    pub fn add<F>(&mut self, name: &str, priority: u8, f: F)
+    where
+        F: 'static + Fn() -> String,
+    {
+        let index = self
+            .widgets
+            .iter()
+            .position(|w| w.priority < priority)
+            .unwrap_or(self.widgets.len());
+        self.widgets.insert(
+            index,
+            Status {
+                name: String::from(name),
+                priority,
+                f: Box::new(f),
+            },
+        );
+    }
+
+    /// Removes the widgets added under `name`.
+    pub fn remove(&mut self, name: &str) {
+        self.widgets.retain(|w| w.name != name);
+    }
+
+    /// The packed lines, separated by `\n`, without trailing empty lines.
+    pub fn pack(&self) -> String {
+        let mut lines: Vec<String> = vec![String::new(); self.lines];
+        let len = |s: &str| s.chars().count();
+
+        for widget in &self.widgets {
+            let text = (widget.f)();
+            if text.is_empty() {
+                continue;
+            }
+
+            let room = |line: &String| {
+                let used = len(line);
+                let separator = if used == 0 { 0 } else { len(SEPARATOR) };
+                self.width.saturating_sub(used + separator)
+            };
+
+            let fits = lines.iter().position(|line| room(line) >= len(&text));
+            let placed = match fits {
+                Some(i) => Some((i, text)),
+                None if self.overflow == Overflow::Truncate => (0..lines.len())
+                    .max_by_key(|i| room(&lines[*i]))
+                    .filter(|i| room(&lines[*i]) >= Overflow::MIN_TRUNCATED)
+                    .map(|i| {
+                        let mut cut: String = text.chars().take(room(&lines[i]) - 1).collect();
+                        cut.push('~');
+                        (i, cut)
+                    }),
+                None => None,
+            };
+
+            if let Some((i, text)) = placed {
+                if !lines[i].is_empty() {
+                    lines[i].push_str(SEPARATOR);
+                }
+                lines[i].push_str(&text);
+            }
+        }
+
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+
+    /// Packs the widgets and passes the text to the sink if it changed. Call once per frame.
+    pub fn update(&mut self) {
+        let text = self.pack();
+        if text != self.shown {
+            (self.sink)(&text);
+            self.shown = text;
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Glyphs used by the built-in widgets.
+//!
+//! Widgets look up the active theme on every render, so switching themes takes effect on the
+//! next redraw.
+
+use crate::sound::Sounds;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use wut::font::icons;
+
+#[derive(PartialEq)]
+pub struct Theme {
+    /// Shown left of the selected item of an open menu.
+    pub prev: &'static str,
+    /// Shown right of the selected item of an open menu.
+    pub next: &'static str,
+    /// Marks a value adjusted with Left/Right while editing.
+    pub prev_next: &'static str,
+    /// Spacing between the selected item and the arrows.
+    pub gap: &'static str,
+    /// Shown in front of the selected item of an open menu.
+    pub item: &'static str,
+    /// Marks the selected item of a menu in list mode, see [crate::Menu::list].
+    pub cursor: &'static str,
+    /// Marks a closed submenu.
+    pub submenu: &'static str,
+    /// Value is at its minimum and can only increase.
+    pub increase: &'static str,
+    /// Value is at its maximum and can only decrease.
+    pub decrease: &'static str,
+    /// Value can be moved in both directions.
+    pub adjust: &'static str,
+    /// Marks an expanded section.
+    pub expanded: &'static str,
+    /// Marks a collapsed section.
+    pub collapsed: &'static str,
+    pub checked: &'static str,
+    pub unchecked: &'static str,
+    pub star_filled: &'static str,
+    pub star_empty: &'static str,
+    /// Marks an item that requires a code.
+    pub locked: &'static str,
+    /// Filled segment of progress bars.
+    pub bar_filled: &'static str,
+    /// Empty segment of progress bars.
+    pub bar_empty: &'static str,
+    /// Levels of a sparkline, lowest first.
+    pub sparkline: &'static [&'static str],
+    /// Shown next to the value of a color.
+    pub swatch: &'static str,
+    /// Separates the menus of a breadcrumb.
+    pub breadcrumb: &'static str,
+    /// Drawn repeatedly by a [crate::Separator].
+    pub rule: &'static str,
+    /// Marks that a list continues above the lines shown.
+    pub more_above: &'static str,
+    /// Marks that a list continues below the lines shown.
+    pub more_below: &'static str,
+    /// Marks an item that does not currently apply, see [crate::MenuItem::enabled].
+    pub disabled: &'static str,
+    /// Marks an item disabled by the [crate::watchdog].
+    pub faulted: &'static str,
+    /// Audio feedback, see [crate::sound].
+    pub sounds: &'static Sounds,
+}
+
+impl Theme {
+    pub const DEFAULT: Theme = Theme {
+        prev: icons::BTN_LEFT,
+        next: icons::BTN_RIGHT,
+        prev_next: "\u{2194}",
+        gap: "\u{3000}",
+        item: "",
+        cursor: "\u{25B6} ",
+        submenu: icons::KBD_RETURN,
+        increase: icons::ARROW_UP,
+        decrease: icons::ARROW_DOWN,
+        adjust: icons::ARROW_UP_DOWN,
+        expanded: "\u{25BC}",
+        collapsed: "\u{25B6}",
+        checked: "X",
+        unchecked: "  ",
+        star_filled: "\u{2605}",
+        star_empty: "\u{2606}",
+        locked: "(locked)",
+        bar_filled: "=",
+        bar_empty: "-",
+        sparkline: &[
+            "\u{2581}", "\u{2582}", "\u{2583}", "\u{2584}", "\u{2585}", "\u{2586}", "\u{2587}",
+            "\u{2588}",
+        ],
+        swatch: "\u{2588}",
+        breadcrumb: " \u{25B8} ",
+        rule: "\u{2500}",
+        more_above: "\u{25B2}",
+        more_below: "\u{25BC}",
+        disabled: "(n/a)",
+        faulted: "\u{26A0}",
+        sounds: &Sounds::DEFAULT,
+    };
+
+    /// ASCII only; rendering does not depend on the system font. Used for snapshots.
+    pub const PLAIN: Theme = Theme {
+        prev: "<",
+        next: ">",
+        prev_next: "<>",
+        gap: " ",
+        item: "",
+        cursor: "> ",
+        submenu: ">>",
+        increase: "+",
+        decrease: "-",
+        adjust: "+-",
+        expanded: "v",
+        collapsed: ">",
+        checked: "X",
+        unchecked: " ",
+        star_filled: "*",
+        star_empty: ".",
+        locked: "(locked)",
+        bar_filled: "=",
+        bar_empty: "-",
+        sparkline: &["_", ".", "-", "=", "#"],
+        swatch: "#",
+        breadcrumb: " > ",
+        rule: "-",
+        more_above: "^",
+        more_below: "v",
+        disabled: "(n/a)",
+        faulted: "(!)",
+        sounds: &Sounds::DEFAULT,
+    };
+
+    /// ASCII only with bold, unambiguous markers for small or low-contrast screens.
+    pub const ACCESSIBLE: Theme = Theme {
+        prev: "<<",
+        next: ">>",
+        prev_next: "<->",
+        gap: "  ",
+        item: "> ",
+        cursor: "> ",
+        submenu: "[MENU]",
+        increase: "[+]",
+        decrease: "[-]",
+        adjust: "[+/-]",
+        expanded: "[OPEN]",
+        collapsed: "[CLOSED]",
+        checked: "[ON]",
+        unchecked: "[OFF]",
+        star_filled: "#",
+        star_empty: "_",
+        locked: "[LOCKED]",
+        bar_filled: "#",
+        bar_empty: "_",
+        sparkline: &["_", ".", "-", "=", "#"],
+        swatch: "#",
+        breadcrumb: " > ",
+        rule: "-",
+        more_above: "^",
+        more_below: "v",
+        disabled: "[DISABLED]",
+        faulted: "[FAULT]",
+        sounds: &Sounds::DEFAULT,
+    };
+
+    /// [Theme::ACCESSIBLE] with doubled spacing around the selected item.
+    pub const ACCESSIBLE_WIDE: Theme = Theme {
+        gap: "    ",
+        ..Theme::ACCESSIBLE
+    };
+}
+
+static DEFAULT: Theme = Theme::DEFAULT;
+static CURRENT: AtomicPtr<Theme> = AtomicPtr::new(&DEFAULT as *const Theme as *mut Theme);
+
+/// The active theme.
+pub fn current() -> &'static Theme {
+    // SAFETY: only ever set from `&'static Theme`.
+    unsafe { &*CURRENT.load(Ordering::Relaxed) }
+}
+
+pub fn set(theme: &'static Theme) {
+    CURRENT.store(theme as *const Theme as *mut Theme, Ordering::Relaxed);
+}
@@ -0,0 +1,109 @@
+//! Input timing of an overlay, in 60 Hz frames.
+//!
+//! Each overlay has its own, see [crate::Overlay::set_timings], which items read from
+//! [crate::MenuContext::timings] while handling input, so changes take effect immediately.
+//! Counters advance by [crate::MenuContext::elapsed] per update, so with a clock the timings are
+//! the same regardless of how often the host updates the overlay.
+
+use wut::gamepad::{Button, State};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    /// Frames a direction has to be held before it starts repeating.
+    pub repeat_delay: u32,
+    /// Frames between repeats once repeating.
+    pub repeat_interval: u32,
+    /// Frames between repeats after holding for a while.
+    pub repeat_interval_fast: u32,
+    /// Frames a button has to be held to count as a long press, e.g. X resetting an item, see
+    /// [crate::Overlay::set_reset_hold].
+    pub long_press: u32,
+    /// Frames within which a second press counts as a double press.
+    pub double_press: u32,
+    /// Frames without a step after which [crate::StepScale::Accelerate] starts over.
+    pub accelerate_pause: u32,
+}
+
+impl Timings {
+    pub const DEFAULT: Timings = Timings {
+        repeat_delay: 60,
+        repeat_interval: 12,
+        repeat_interval_fast: 3,
+        long_press: 30,
+        double_press: 15,
+        accelerate_pause: 15,
+    };
+
+    /// Slower repeat and more lenient presses.
+    pub const RELAXED: Timings = Timings {
+        repeat_delay: 90,
+        repeat_interval: 30,
+        repeat_interval_fast: 12,
+        long_press: 60,
+        double_press: 30,
+        accelerate_pause: 30,
+    };
+
+    /// Frames until the next repeat, by how long the direction has been held.
+    pub fn repeat_interval(&self, held: u32) -> u32 {
+        if held < 2 * self.repeat_delay {
+            self.repeat_delay
+        } else if held < 4 * self.repeat_delay {
+            self.repeat_interval
+        } else {
+            self.repeat_interval_fast
+        }
+        .max(1)
+    }
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Hold-to-repeat of a pair of opposite directions, e.g. Left/Right.
+#[derive(Debug, Default)]
+pub(crate) struct Repeat {
+    held: u32,
+    next: u32,
+}
+
+impl Repeat {
+    /// Stops a repeat in progress, e.g. when the widget loses focus.
+    pub(crate) fn reset(&mut self) {
+        self.held = 0;
+    }
+
+    /// Movement for this frame (`true` towards `forward`): on a press, then repeating faster the
+    /// longer the direction is held, as configured by `timings`. `elapsed` frames passed since the
+    /// previous one.
+    pub(crate) fn step(
+        &mut self,
+        input: &State,
+        back: Button,
+        forward: Button,
+        timings: &Timings,
+        elapsed: u32,
+    ) -> Option<bool> {
+        if input.trigger.contains(back) || input.trigger.contains(forward) {
+            self.held = 0;
+            self.next = timings.repeat_delay;
+            return Some(!input.trigger.contains(back));
+        }
+
+        if !input.hold.contains(back) && !input.hold.contains(forward) {
+            self.held = 0;
+            return None;
+        }
+
+        self.held = self.held.saturating_add(elapsed);
+        if self.held < self.next {
+            return None;
+        }
+
+        self.next += timings.repeat_interval(self.held);
+        Some(!input.hold.contains(back))
+    }
+}
@@ -0,0 +1,365 @@
+//! Disables items whose callbacks misbehave.
+//!
+//! An item is faulted when its callback runs longer than the budget set with
+//! [crate::Overlay::set_callback_budget], or when the host reports a panic with [report_panic]
+//! from its panic hook. Menus show faulted items with [crate::theme::Theme::faulted] and no
+//! longer pass input to them, so their callbacks are not invoked until they are re-enabled, e.g.
+//! from the [Faults] submenu.
+//!
+//! Faults only last for the session unless they are kept in a [Storage] with [persist]. Panics
+//! abort on the console, so without that a faulted item would be run again after the restart.
+//!
+//! Faulted items are recognized by their address and name, i.e. their [MenuItem::id] or label.
+//! The fault of an item dropped since stays listed until it is re-enabled, but does not carry over
+//! to an item with another name allocated at the same address.
+//!
+//! Like the rest of the overlay, the watchdog must only be used from one thread.
+
+use crate::{MenuContext, MenuItem, Node, Value, global::Global, name, persist::Storage, theme};
+use core::{cell::RefCell, fmt::Write};
+use wut::{
+    alloc::{boxed::Box, rc::Rc},
+    gamepad::State,
+    prelude::*,
+};
+
+struct Fault {
+    /// Address of the item, see [addr].
+    node: usize,
+    label: String,
+    /// See [crate::name].
+    name: String,
+    reason: String,
+}
+
+struct Watchdog {
+    faults: Vec<Fault>,
+    /// Address of the item whose callbacks currently run, with its name while faults are kept,
+    /// see [crate::name].
+    running: Option<(usize, Option<String>)>,
+    kept: Option<Kept>,
+}
+
+/// Where faults are kept across sessions, see [persist].
+struct Kept {
+    storage: Box<dyn Storage + Send>,
+    key: String,
+}
+
+static WATCHDOG: Global<Watchdog> = Global::new(Watchdog {
+    faults: Vec::new(),
+    running: None,
+    kept: None,
+});
+
+/// Address identifying `node` while it is alive. Kept instead of a [wut::alloc::rc::Weak], whose
+/// counts must not be touched from other threads.
+fn addr(node: &Node) -> usize {
+    Rc::as_ptr(node) as usize
+}
+
+impl Watchdog {
+    /// Index of the fault of `node`. Faults left by a dropped item whose address `node` reuses are
+    /// told apart by their name, if they have one, and removed.
+    fn find(&mut self, node: &Node) -> Option<usize> {
+        let addr = addr(node);
+        let i = self.faults.iter().position(|f| f.node == addr)?;
+        // A borrowed item is the one running, e.g. panicking, and can only be the faulted one.
+        let Ok(item) = node.try_borrow() else {
+            return Some(i);
+        };
+        if self.faults[i].name.is_empty() || name(&**item) == self.faults[i].name {
+            return Some(i);
+        }
+
+        self.faults.remove(i);
+        self.save();
+        None
+    }
+
+    /// Writes the faults to the storage given to [persist], if any.
+    fn save(&self) {
+        let Some(kept) = &self.kept else {
+            return;
+        };
+
+        let mut out = String::new();
+        for fault in &self.faults {
+            let reason = fault.reason.replace(['\t', '\n'], " ");
+            let _ = writeln!(out, "{}\t{}", fault.name, reason);
+        }
+        let _ = kept.storage.save(&kept.key, out.as_bytes());
+    }
+}
+
+/// Whether `node` is faulted.
+pub fn is_faulted(node: &Node) -> bool {
+    WATCHDOG.with(|w| w.find(node).is_some())
+}
+
+/// Marks `node` as faulted for `reason`.
+pub fn fault(node: &Node, reason: &str) {
+    let names = node.try_borrow().map(|n| (n.label(), name(&**n))).ok();
+    WATCHDOG.with(|w| {
+        if w.find(node).is_none() {
+            fault_in(w, addr(node), names, reason);
+        }
+    });
+}
+
+/// Faults the item at `node` with its label and [crate::name], if they could be taken.
+fn fault_in(w: &mut Watchdog, node: usize, names: Option<(String, String)>, reason: &str) {
+    if w.faults.iter().any(|f| f.node == node) {
+        return;
+    }
+
+    // An item that panicked is still borrowed, so fall back to the name taken by `run`.
+    let (label, name) = names
+        .or_else(|| {
+            w.running
+                .as_ref()
+                .filter(|(running, _)| *running == node)
+                .and_then(|(_, name)| name.clone())
+                .map(|name| (name.clone(), name))
+        })
+        .unwrap_or_default();
+
+    w.faults.push(Fault {
+        node,
+        label,
+        name,
+        reason: String::from(reason),
+    });
+    w.save();
+}
+
+/// Re-enables `node`. Returns whether it was faulted.
+pub fn enable(node: &Node) -> bool {
+    WATCHDOG.with(|w| {
+        let Some(i) = w.find(node) else {
+            return false;
+        };
+        w.faults.remove(i);
+        w.save();
+        true
+    })
+}
+
+/// Labels and reasons of the faulted items.
+pub fn faults() -> Vec<(String, String)> {
+    WATCHDOG.with(|w| {
+        w.faults
+            .iter()
+            .map(|f| (f.label.clone(), f.reason.clone()))
+            .collect()
+    })
+}
+
+/// Keeps faults in `storage` under `key` from now on, and faults the items below `root` that were
+/// faulted when they were last kept there. Items are recognized by their [MenuItem::id], or
+/// their label if they have none.
+///
+/// With faults kept, a panic reported with [report_panic] is saved before the console aborts,
+/// and the item stays disabled after the restart.
+pub fn persist<S: Storage + Send + 'static>(root: &Node, storage: S, key: &str) {
+    type Found = Vec<(Node, (String, String), String)>;
+
+    fn walk(node: &Node, saved: &[(String, String)], out: &mut Found) {
+        let Ok(item) = node.try_borrow() else {
+            return;
+        };
+        let name = name(&**item);
+        if let Some((_, reason)) = saved.iter().find(|(saved, _)| *saved == name) {
+            out.push((node.clone(), (item.label(), name), reason.clone()));
+        }
+        for child in item.children() {
+            walk(child, saved, out);
+        }
+    }
+
+    let saved: Vec<(String, String)> = storage
+        .load(key)
+        .and_then(|data| String::from_utf8(data).ok())
+        .map(|text| {
+            text.lines()
+                .filter_map(|line| line.split_once('\t'))
+                .map(|(name, reason)| (String::from(name), String::from(reason)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut found = vec![];
+    walk(root, &saved, &mut found);
+
+    WATCHDOG.with(|w| {
+        w.kept = Some(Kept {
+            storage: Box::new(storage),
+            key: String::from(key),
+        });
+        for (node, names, reason) in found {
+            if w.find(&node).is_none() {
+                fault_in(w, addr(&node), Some(names), &reason);
+            }
+        }
+    });
+}
+
+/// Faults the item whose callbacks were running when the panic happened, for calling from the
+/// host's panic hook. Returns whether there was one. With [persist], the fault is saved right
+/// away.
+pub fn report_panic(message: &str) -> bool {
+    WATCHDOG
+        .try_with(|w| {
+            let Some((node, _)) = w.running else {
+                return false;
+            };
+            fault_in(w, node, None, message);
+            w.running = None;
+            true
+        })
+        .unwrap_or(false)
+}
+
+/// Runs `f` as running callbacks of `node`, so a panic in it is attributed to `node`. Nested runs
+/// attribute to the innermost node.
+pub(crate) fn run<R>(node: &Node, f: impl FnOnce() -> R) -> R {
+    let name = if WATCHDOG.with(|w| w.kept.is_some()) {
+        node.try_borrow().ok().map(|n| name(&**n))
+    } else {
+        None
+    };
+    let outer = WATCHDOG.with(|w| w.running.replace((addr(node), name)));
+    let result = f();
+    WATCHDOG.with(|w| w.running = outer);
+    result
+}
+
+/// Ticks `node` with [run], unless it is faulted or borrowed. Returns whether its rendering
+/// changed.
+pub(crate) fn tick(node: &Node, ctx: &mut MenuContext) -> bool {
+    !is_faulted(node)
+        && run(node, || {
+            node.try_borrow_mut()
+                .is_ok_and(|mut item| item.tick_ctx(ctx))
+        })
+}
+
+/// Sets the value of `node` like [MenuItem::set_value] with [run], unless it is faulted or
+/// borrowed. Returns whether the value was accepted.
+pub(crate) fn set_value(node: &Node, value: &Value) -> bool {
+    !is_faulted(node)
+        && run(node, || {
+            node.try_borrow_mut()
+                .is_ok_and(|mut item| item.set_value(value))
+        })
+}
+
+/// Submenu listing the faulted items with their reason. Left/Right pick an item, A re-enables it
+/// and B closes the list.
+pub struct Faults {
+    name: String,
+    pos: usize,
+    open: bool,
+    /// Number of faults when last ticked.
+    count: usize,
+}
+
+impl Faults {
+    pub fn new(name: &str) -> Node {
+        Rc::new(RefCell::new(Box::new(Self {
+            name: String::from(name),
+            pos: 0,
+            open: false,
+            count: 0,
+        })))
+    }
+
+    fn count() -> usize {
+        WATCHDOG.with(|w| w.faults.len())
+    }
+}
+
+impl MenuItem for Faults {
+    fn render(&self) -> String {
+        let theme = theme::current();
+
+        if !self.open {
+            return format!("{} ({}) {}", self.name, Self::count(), theme.submenu);
+        }
+
+        let mut out = String::new();
+        WATCHDOG.with(|w| match w.faults.get(self.pos) {
+            Some(fault) => {
+                let _ = write!(
+                    out,
+                    "{}{}{} {}: {}{}{}",
+                    theme.prev,
+                    theme.gap,
+                    theme.faulted,
+                    fault.label,
+                    fault.reason,
+                    theme.gap,
+                    theme.next
+                );
+            }
+            None => out.push_str("No faults"),
+        });
+        out
+    }
+
+    fn control(&mut self, input: State, stack: &mut Vec<Node>) -> bool {
+        use wut::gamepad::Button as B;
+
+        if !self.open {
+            return false;
+        }
+
+        let count = Self::count();
+        if input.trigger.contains(B::B) {
+            self.blur();
+            stack.pop();
+        } else if count == 0 {
+            return false;
+        } else if input.trigger.contains(B::A) {
+            let i = self.pos.min(count - 1);
+            WATCHDOG.with(|w| {
+                w.faults.remove(i);
+                w.save();
+            });
+            self.pos = self.pos.min(count.saturating_sub(2));
+        } else if input.trigger.contains(B::Right) {
+            self.pos = (self.pos + 1) % count;
+        } else if input.trigger.contains(B::Left) {
+            self.pos = (self.pos + count - 1) % count;
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn tick(&mut self) -> bool {
+        let count = Self::count();
+        let changed = count != self.count;
+        self.count = count;
+        self.pos = self.pos.min(count.saturating_sub(1));
+        changed
+    }
+
+    fn focus(&mut self) {
+        self.open = true;
+        self.pos = 0;
+    }
+
+    fn blur(&mut self) {
+        self.open = false;
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn label(&self) -> String {
+        self.name.clone()
+    }
+}
@@ -0,0 +1,87 @@
+//! Builds overlay widgets from WUPS config item definitions.
+//!
+//! Each widget reads its initial value from and writes changes back to the storage key of its
+//! config item, so the WUPS config menu and the overlay stay in sync. Values are stored as
+//! decimal text.
+
+use crate::{Menu, Node, Number, Select, Toggle, persist::Storage};
+use wut::{alloc::rc::Rc, prelude::*};
+
+/// A WUPS config item together with the storage key it is saved under.
+pub enum ConfigItem<'a> {
+    Boolean {
+        key: &'a str,
+        name: &'a str,
+        default: bool,
+    },
+    IntegerRange {
+        key: &'a str,
+        name: &'a str,
+        default: i32,
+        min: i32,
+        max: i32,
+    },
+    /// The stored value is the index into `values`.
+    MultipleValues {
+        key: &'a str,
+        name: &'a str,
+        default: usize,
+        values: &'a [&'a str],
+    },
+    Category {
+        name: &'a str,
+        items: Vec<ConfigItem<'a>>,
+    },
+}
+
+fn load<T: core::str::FromStr>(storage: &dyn Storage, key: &str) -> Option<T> {
+    let data = storage.load(key)?;
+    core::str::from_utf8(&data).ok()?.parse().ok()
+}
+
+fn save(storage: &dyn Storage, key: &str, value: impl core::fmt::Display) {
+    let _ = storage.save(key, format!("{}", value).as_bytes());
+}
+
+/// Widgets for `items`, categories becoming submenus.
+pub fn import(items: &[ConfigItem], storage: Rc<dyn Storage>) -> Vec<Node> {
+    items
+        .iter()
+        .map(|item| match item {
+            ConfigItem::Boolean { key, name, default } => {
+                let value = load::<u8>(&*storage, key).map_or(*default, |v| v != 0);
+                let (key, storage) = (String::from(*key), storage.clone());
+                Toggle::new(name, value, move |value| save(&*storage, &key, value as u8))
+            }
+            ConfigItem::IntegerRange {
+                key,
+                name,
+                default,
+                min,
+                max,
+            } => {
+                let value = load(&*storage, key).unwrap_or(*default).clamp(*min, *max);
+                let (key, storage) = (String::from(*key), storage.clone());
+                Number::new(name, value, 1, *min, *max, move |value| {
+                    save(&*storage, &key, value)
+                })
+            }
+            ConfigItem::MultipleValues {
+                key,
+                name,
+                default,
+                values,
+            } => {
+                let index = load(&*storage, key)
+                    .filter(|i| i < &values.len())
+                    .unwrap_or(*default);
+                let (key, storage) = (String::from(*key), storage.clone());
+                Select::<String>::with_index(name, values.to_vec(), index, move |index, _| {
+                    save(&*storage, &key, index)
+                })
+                .into()
+            }
+            ConfigItem::Category { name, items } => Menu::new(name, import(items, storage.clone())),
+        })
+        .collect()
+}
@@ -0,0 +1,177 @@
+//! Menu behaviour driven through the [Harness] of the simulator.
+//!
+//! Run with `cargo test --no-default-features --features simulator`.
+
+#![cfg(feature = "simulator")]
+
+use overlay::{Button, Menu, Number, Text, Value, persist, simulator::Harness};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+use wut::{flagset::FlagSet, gamepad::Button as B};
+
+fn release() -> FlagSet<B> {
+    FlagSet::default()
+}
+
+// region: Stack
+
+#[test]
+fn removing_the_entered_submenu_pops_it() {
+    let sub = Menu::new("Sub", vec![Button::new("Inner", || {})]);
+    let root = Menu::typed("Root", vec![sub]);
+    let mut h = Harness::new(root.node());
+
+    h.press(B::A);
+    assert_eq!(h.depth(), 2);
+
+    root.remove(0);
+    h.frame(release());
+    assert_eq!(h.depth(), 1);
+}
+
+#[test]
+fn reinserting_the_entered_submenu_keeps_it() {
+    let sub = Menu::new("Sub", vec![Button::new("Inner", || {})]);
+    let root = Menu::typed("Root", vec![sub]);
+    let mut h = Harness::new(root.node());
+
+    h.press(B::A);
+    let sub = root.remove(0).unwrap();
+    root.push(sub);
+    h.frame(release());
+    assert_eq!(h.depth(), 2);
+}
+
+// endregion
+
+// region: X
+
+#[test]
+fn short_x_press_edits_digits() {
+    let number = Number::typed("Value", 5, 1, 0, 999, |_| {});
+    let mut h = Harness::new(Menu::new("Root", vec![number.node()]));
+    h.overlay().set_reset_hold(true);
+
+    h.run(B::X).run(release());
+    assert_eq!(h.depth(), 2);
+}
+
+#[test]
+fn holding_x_resets_without_editing_digits() {
+    let number = Number::typed("Value", 5, 1, 0, 999, |_| {});
+    let mut h = Harness::new(Menu::new("Root", vec![number.node()]));
+    h.overlay().set_reset_hold(true);
+    let long_press = h.overlay().timings().long_press;
+
+    h.run(B::Up).run(release());
+    assert_eq!(number.get(), 6);
+
+    for _ in 0..long_press {
+        h.run(B::X);
+    }
+    h.run(release());
+    assert_eq!(number.get(), 5);
+    assert_eq!(h.depth(), 1);
+}
+
+// endregion
+
+// region: Persistence
+
+#[test]
+fn saved_values_are_restored() {
+    let number = Number::typed("Value", 5, 1, 0, 9, |_| {});
+    let root = Menu::new("Root", vec![number.node()]);
+    let storage = persist::Memory::new();
+    let mut h = Harness::new(root.clone());
+
+    h.press(B::Up);
+    assert_eq!(number.get(), 6);
+    persist::save(&root, &storage, "values").unwrap();
+
+    number.set_value(&Value::Int(2));
+    assert_eq!(persist::restore(&root, &storage, "values"), 1);
+    assert_eq!(number.get(), 6);
+}
+
+// endregion
+
+// region: Search
+
+#[test]
+fn search_narrows_the_entries() {
+    let root = Menu::searchable(
+        "Root",
+        vec![
+            Button::new("Apple", || {}),
+            Button::new("Banana", || {}),
+            Button::new("Cherry", || {}),
+        ],
+    );
+    let mut h = Harness::new(root);
+
+    // Types "an": `a` is picked first, `n` 13 characters later.
+    h.run(B::Y).run(release());
+    h.run(B::X).run(release());
+    for _ in 0..13 {
+        h.run(B::Up).run(release());
+    }
+    h.run(B::X).run(release());
+    h.run(B::A).run(release());
+    assert_eq!(h.depth(), 1);
+    assert!(h.line().contains("Banana"));
+
+    h.run(B::Right).run(release());
+    assert!(h.line().contains("Banana"));
+}
+
+// endregion
+
+// region: Cursor
+
+#[test]
+fn cursor_skips_display_only_items() {
+    let root = Menu::new(
+        "Root",
+        vec![
+            Text::new(|| String::from("Info")),
+            Button::new("First", || {}),
+            Text::new(|| String::from("More info")),
+            Button::new("Second", || {}),
+        ],
+    );
+    let mut h = Harness::new(root);
+    assert!(h.line().contains("First"));
+
+    h.press(B::Right);
+    assert!(h.line().contains("Second"));
+
+    h.press(B::Right);
+    assert!(h.line().contains("First"));
+}
+
+// endregion
+
+// region: Bindings
+
+#[test]
+fn bound_number_follows_the_shared_value() {
+    let shared = Arc::new(AtomicU32::new(3));
+    let number = Number::bind("Volume", shared.clone(), 1, 0, 10);
+    let mut h = Harness::new(Menu::new("Root", vec![number.node()]));
+
+    h.run(B::Up).run(release());
+    assert_eq!(shared.load(Ordering::Relaxed), 4);
+
+    shared.store(7, Ordering::Relaxed);
+    h.run(release());
+    assert_eq!(number.get(), 7);
+
+    shared.store(1000, Ordering::Relaxed);
+    h.run(release());
+    assert_eq!(number.get(), 10);
+}
+
+// endregion